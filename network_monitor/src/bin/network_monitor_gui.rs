@@ -1,7 +1,8 @@
-#![windows_subsystem = "windows"]
+#![cfg_attr(windows, windows_subsystem = "windows")]
 
 use std::process::Command;
 use std::env;
+#[cfg(windows)]
 use std::os::windows::process::CommandExt;
 
 fn main() {
@@ -11,15 +12,20 @@ fn main() {
         .and_then(|path| path.parent().map(|p| p.to_path_buf()))
         .unwrap_or_else(|| env::current_dir().unwrap());
     
-    // network_monitor.exe 경로 구성
-    let exe_path = exe_dir.join("network_monitor.exe");
+    // network_monitor 실행 파일 경로 구성
+    let exe_path = exe_dir.join(format!("network_monitor{}", env::consts::EXE_SUFFIX));
     
+    let mut command = Command::new(exe_path);
+    command.arg("gui");
+
     // GUI 모드로 실행 (CREATE_NO_WINDOW 플래그 사용)
-    const CREATE_NO_WINDOW: u32 = 0x08000000;
-    let status = Command::new(exe_path)
-        .arg("gui")
-        .creation_flags(CREATE_NO_WINDOW)
-        .status();
+    #[cfg(windows)]
+    {
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let status = command.status();
     
     match status {
         Ok(exit_status) => {
@@ -0,0 +1,394 @@
+use super::NetworkTarget;
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, Local, NaiveDateTime, NaiveTime, Timelike, Weekday};
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// cron으로 지정한 점검 시간의 최대 길이 (분, 7일)
+const MAX_CRON_DURATION_MIN: u64 = 7 * 24 * 60;
+
+/// 복구 작업과 알림을 멈추는 점검 시간입니다. 점검 중에도 대상 확인과 상태 기록은 계속합니다.
+///
+/// 시작 시각을 cron 표현식과 길이(`cron`, `duration_min`)로 지정하거나,
+/// 요일과 시각 범위(`days`, `start`, `end`)로 지정합니다.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MaintenanceWindow {
+    pub name: String,
+    /// 점검 시작 시각 cron 표현식 ("분 시 일 월 요일", 로컬 시간)
+    pub cron: Option<String>,
+    /// cron으로 시작한 점검 시간의 길이 (분)
+    pub duration_min: Option<u64>,
+    /// 점검 요일 (mon, tue, ..., sun). 비우면 매일
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<String>,
+    /// 시작 시각 ("HH:MM"). start와 end를 생략하면 하루 종일
+    pub start: Option<String>,
+    /// 종료 시각 ("HH:MM", 포함하지 않음). start보다 이르면 다음 날 종료
+    pub end: Option<String>,
+    /// 점검 대상 이름 (tags와 모두 비어 있으면 모든 대상)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<String>,
+    /// 점검 대상 태그
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// 처음 해석한 일정 (설정 검증에서 채워짐)
+    #[serde(skip)]
+    parsed: OnceLock<Result<Schedule, String>>,
+}
+
+impl MaintenanceWindow {
+    /// 대상이 이 점검 시간에 포함되는지 여부
+    pub fn includes(&self, target: &NetworkTarget) -> bool {
+        (self.targets.is_empty() && self.tags.is_empty())
+            || self.targets.contains(&target.name)
+            || target.tags.iter().any(|tag| self.tags.contains(tag))
+    }
+
+    /// 해석한 일정. 처음 호출할 때 한 번만 해석하며, 설정이 잘못되었으면 이유를 반환합니다.
+    pub fn schedule(&self) -> Result<&Schedule, String> {
+        self.parsed
+            .get_or_init(|| {
+                let parsed = self.parse_schedule();
+                if let Err(reason) = &parsed {
+                    log::warn!("점검 시간 '{}'의 일정이 잘못되어 적용하지 않음: {}", self.name, reason);
+                }
+                parsed
+            })
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+
+    fn parse_schedule(&self) -> Result<Schedule, String> {
+        let ranged = !self.days.is_empty() || self.start.is_some() || self.end.is_some();
+        match (&self.cron, ranged) {
+            (Some(_), true) => Err("cron과 days/start/end는 함께 사용할 수 없음".to_string()),
+            (Some(cron), false) => {
+                let duration = match self.duration_min {
+                    Some(duration) if (1..=MAX_CRON_DURATION_MIN).contains(&duration) => duration,
+                    Some(_) => return Err(format!("duration_min은 1 이상 {} 이하여야 함", MAX_CRON_DURATION_MIN)),
+                    None => return Err("cron에는 duration_min이 필요함".to_string()),
+                };
+                Ok(Schedule::Cron {
+                    cron: CronExpression::parse(cron)?,
+                    duration_min: duration,
+                })
+            }
+            (None, true) => {
+                if self.duration_min.is_some() {
+                    return Err("duration_min은 cron과 함께 사용해야 함".to_string());
+                }
+                let days = self
+                    .days
+                    .iter()
+                    .map(|day| day.parse::<Weekday>().map_err(|_| format!("알 수 없는 요일: {}", day)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let (start, end) = match (&self.start, &self.end) {
+                    (Some(start), Some(end)) => (parse_time(start)?, parse_time(end)?),
+                    (None, None) => (NaiveTime::MIN, NaiveTime::MIN),
+                    _ => return Err("start와 end는 함께 지정해야 함".to_string()),
+                };
+                Ok(Schedule::Range { days, start, end })
+            }
+            (None, false) => Err("cron 또는 days/start/end 중 하나를 지정해야 함".to_string()),
+        }
+    }
+
+    /// `now`가 점검 시간 안인지 여부.
+    /// 잘못된 일정은 설정을 불러올 때 거부되므로, 검증하지 않은 설정에서만 (처음 한 번 경고하고) false를 반환합니다.
+    pub fn is_active(&self, now: &DateTime<Local>) -> bool {
+        self.schedule().is_ok_and(|schedule| schedule.is_active(now.naive_local()))
+    }
+}
+
+/// 해석한 점검 일정입니다.
+#[derive(Debug, Clone)]
+pub enum Schedule {
+    /// cron 표현식에 맞는 시각부터 `duration_min`분 동안
+    Cron { cron: CronExpression, duration_min: u64 },
+    /// `days` 요일(비어 있으면 매일)의 `start`부터 `end`까지. `end`가 `start`보다 이르거나 같으면 다음 날 `end`까지
+    Range { days: Vec<Weekday>, start: NaiveTime, end: NaiveTime },
+}
+
+impl Schedule {
+    /// 로컬 시각 `now`가 점검 시간 안인지 여부
+    pub fn is_active(&self, now: NaiveDateTime) -> bool {
+        match self {
+            Schedule::Cron { cron, duration_min } => {
+                // 지난 duration_min분 안에 시작 시각이 있었는지 확인
+                let Some(minute) = now.with_second(0).and_then(|t| t.with_nanosecond(0)) else {
+                    return false;
+                };
+                let earliest = minute - ChronoDuration::minutes(*duration_min as i64 - 1);
+                cron.latest_match(minute, earliest).is_some()
+            }
+            Schedule::Range { days, start, end } => {
+                let on = |day: Weekday| days.is_empty() || days.contains(&day);
+                let time = now.time();
+                let today = now.weekday();
+                if start < end {
+                    on(today) && *start <= time && time < *end
+                } else {
+                    (on(today) && time >= *start) || (on(today.pred()) && time < *end)
+                }
+            }
+        }
+    }
+}
+
+/// 5개 필드("분 시 일 월 요일") cron 표현식입니다.
+/// 필드마다 `*`, 숫자, 범위(`1-5`), 목록(`1,3`), 간격(`*/15`, `0-30/10`)을 사용할 수 있고,
+/// 월과 요일은 영문 약어(jan, mon 등)도 사용할 수 있습니다. 요일의 0과 7은 일요일입니다.
+#[derive(Debug, Clone)]
+pub struct CronExpression {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    /// 일 필드가 `*`가 아님
+    day_of_month_restricted: bool,
+    /// 요일 필드가 `*`가 아님
+    day_of_week_restricted: bool,
+}
+
+const MONTH_NAMES: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+const WEEKDAY_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+impl CronExpression {
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            return Err(format!("cron 표현식은 필드 5개가 필요함: {}", expression));
+        };
+        let mut days_of_week = parse_field(day_of_week, 0, 7, &WEEKDAY_NAMES, 0)?;
+        // 7도 일요일
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week |= 1;
+        }
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59, &[], 0)?,
+            hours: parse_field(hour, 0, 23, &[], 0)?,
+            days_of_month: parse_field(day_of_month, 1, 31, &[], 1)?,
+            months: parse_field(month, 1, 12, &MONTH_NAMES, 1)?,
+            days_of_week,
+            day_of_month_restricted: day_of_month != "*",
+            day_of_week_restricted: day_of_week != "*",
+        })
+    }
+
+    /// 시각(분 단위)이 표현식에 맞는지 여부.
+    /// 일과 요일을 모두 지정하면 cron과 같이 둘 중 하나만 맞아도 됩니다.
+    pub fn matches(&self, time: NaiveDateTime) -> bool {
+        self.matches_day(time) && self.minutes & (1 << time.minute()) != 0 && self.hours & (1 << time.hour()) != 0
+    }
+
+    /// `earliest`부터 `latest`까지(분 단위, 양 끝 포함) 표현식에 맞는 가장 늦은 시각.
+    /// 맞지 않는 날과 시는 통째로 건너뛰므로 긴 구간도 모든 분을 확인하지 않습니다.
+    pub fn latest_match(&self, latest: NaiveDateTime, earliest: NaiveDateTime) -> Option<NaiveDateTime> {
+        let mut time = latest;
+        while time >= earliest {
+            let start_of_day = time.date().and_time(NaiveTime::MIN);
+            let start_of_hour = start_of_day + ChronoDuration::hours(time.hour() as i64);
+            if !self.matches_day(time) {
+                time = start_of_day - ChronoDuration::minutes(1);
+            } else if self.hours & (1 << time.hour()) == 0 {
+                time = start_of_hour - ChronoDuration::minutes(1);
+            } else if self.matches(time) {
+                return Some(time);
+            } else {
+                time -= ChronoDuration::minutes(1);
+            }
+        }
+        None
+    }
+
+    /// 날짜(일, 월, 요일)가 표현식에 맞는지 여부
+    fn matches_day(&self, time: NaiveDateTime) -> bool {
+        let bit = |mask: u64, value: u32| mask & (1 << value) != 0;
+        let day_of_month = bit(self.days_of_month, time.day());
+        let day_of_week = bit(self.days_of_week, time.weekday().num_days_from_sunday());
+        let day = if self.day_of_month_restricted && self.day_of_week_restricted {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        };
+        day && bit(self.months, time.month())
+    }
+}
+
+/// cron 필드 하나를 값별 비트 집합으로 해석합니다. `names[i]`는 `name_base + i` 값의 이름입니다.
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str], name_base: u32) -> Result<u64, String> {
+    let value = |text: &str| -> Result<u32, String> {
+        let lower = text.to_ascii_lowercase();
+        let value = match names.iter().position(|name| *name == lower) {
+            Some(index) => index as u32 + name_base,
+            None => text.parse().map_err(|_| format!("cron 필드 '{}'의 값을 해석할 수 없음: {}", field, text))?,
+        };
+        if !(min..=max).contains(&value) {
+            return Err(format!("cron 필드 '{}'의 값 {}이(가) 범위({}-{})를 벗어남", field, value, min, max));
+        }
+        Ok(value)
+    };
+
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step.parse().map_err(|_| format!("cron 필드 '{}'의 간격을 해석할 수 없음: {}", field, step))?;
+                if step == 0 {
+                    return Err(format!("cron 필드 '{}'의 간격은 1 이상이어야 함", field));
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (first, last) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((first, last)) => (value(first)?, value(last)?),
+                // 간격이 있으면 시작 값부터 최대값까지
+                None if step > 1 => (value(range)?, max),
+                None => {
+                    let single = value(range)?;
+                    (single, single)
+                }
+            },
+        };
+        if first > last {
+            return Err(format!("cron 필드 '{}'의 범위가 잘못됨: {}", field, range));
+        }
+        for value in (first..=last).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+/// "HH:MM" 시각을 해석합니다.
+fn parse_time(text: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(text, "%H:%M").map_err(|_| format!("시각은 HH:MM 형식이어야 함: {}", text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap().and_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    fn window(toml: &str) -> MaintenanceWindow {
+        toml::from_str(&format!("name = \"test\"\n{}", toml)).unwrap()
+    }
+
+    #[test]
+    fn parses_lists_ranges_steps_and_names() {
+        let cron = CronExpression::parse("*/15 9-17 * jan-mar mon,wed").unwrap();
+        // 2024-01-01은 월요일
+        assert!(cron.matches(at(2024, 1, 1, 9, 0)));
+        assert!(cron.matches(at(2024, 1, 3, 17, 45)));
+        assert!(!cron.matches(at(2024, 1, 1, 9, 10)));
+        assert!(!cron.matches(at(2024, 1, 2, 9, 0)));
+        assert!(!cron.matches(at(2024, 4, 1, 9, 0)));
+
+        let cron = CronExpression::parse("5-30/10 0 * * *").unwrap();
+        for minute in [5, 15, 25] {
+            assert!(cron.matches(at(2024, 1, 1, 0, minute)));
+        }
+        assert!(!cron.matches(at(2024, 1, 1, 0, 35)));
+
+        // 단일 값의 간격은 최대값까지
+        let cron = CronExpression::parse("50/5 * * * *").unwrap();
+        assert!(cron.matches(at(2024, 1, 1, 0, 55)));
+        assert!(!cron.matches(at(2024, 1, 1, 0, 0)));
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        for expression in ["* * * *", "60 * * * *", "* 24 * * *", "* * 0 * *", "*/0 * * * *", "5-1 * * * *", "* * * foo *", "* * * * 8"] {
+            assert!(CronExpression::parse(expression).is_err(), "{}", expression);
+        }
+    }
+
+    #[test]
+    fn seven_is_sunday() {
+        // 2024-01-07은 일요일
+        for expression in ["0 3 * * 0", "0 3 * * 7", "0 3 * * sun"] {
+            let cron = CronExpression::parse(expression).unwrap();
+            assert!(cron.matches(at(2024, 1, 7, 3, 0)), "{}", expression);
+            assert!(!cron.matches(at(2024, 1, 8, 3, 0)), "{}", expression);
+        }
+    }
+
+    #[test]
+    fn day_of_month_or_day_of_week_when_both_restricted() {
+        // 매월 1일 또는 금요일
+        let cron = CronExpression::parse("0 0 1 * fri").unwrap();
+        assert!(cron.matches(at(2024, 1, 1, 0, 0))); // 월요일, 1일
+        assert!(cron.matches(at(2024, 1, 5, 0, 0))); // 금요일
+        assert!(!cron.matches(at(2024, 1, 2, 0, 0)));
+
+        // 한쪽만 지정하면 지정한 쪽만 확인
+        let cron = CronExpression::parse("0 0 1 * *").unwrap();
+        assert!(!cron.matches(at(2024, 1, 5, 0, 0)));
+        let cron = CronExpression::parse("0 0 * * fri").unwrap();
+        assert!(!cron.matches(at(2024, 1, 1, 0, 0)));
+    }
+
+    #[test]
+    fn cron_schedule_lasts_duration() {
+        // 일요일 03:00부터 60분
+        let schedule = window("cron = \"0 3 * * 7\"\nduration_min = 60").schedule().unwrap().clone();
+        assert!(!schedule.is_active(at(2024, 1, 7, 2, 59)));
+        assert!(schedule.is_active(at(2024, 1, 7, 3, 0)));
+        assert!(schedule.is_active(at(2024, 1, 7, 3, 59)));
+        assert!(!schedule.is_active(at(2024, 1, 7, 4, 0)));
+        assert!(!schedule.is_active(at(2024, 1, 8, 3, 30)));
+
+        // 며칠에 걸친 점검 시간: 금요일 22:00부터 주말 내내
+        let schedule = window("cron = \"0 22 * * fri\"\nduration_min = 3480").schedule().unwrap().clone();
+        assert!(schedule.is_active(at(2024, 1, 6, 12, 0)));
+        assert!(schedule.is_active(at(2024, 1, 8, 7, 59)));
+        assert!(!schedule.is_active(at(2024, 1, 8, 8, 0)));
+        assert!(!schedule.is_active(at(2024, 1, 5, 21, 59)));
+    }
+
+    #[test]
+    fn overnight_range_ends_next_day() {
+        // 토요일 23:00부터 일요일 02:00까지
+        let schedule = window("days = [\"sat\"]\nstart = \"23:00\"\nend = \"02:00\"").schedule().unwrap().clone();
+        assert!(!schedule.is_active(at(2024, 1, 6, 22, 59)));
+        assert!(schedule.is_active(at(2024, 1, 6, 23, 0)));
+        assert!(schedule.is_active(at(2024, 1, 7, 1, 59)));
+        assert!(!schedule.is_active(at(2024, 1, 7, 2, 0)));
+        // 일요일 밤은 포함하지 않음
+        assert!(!schedule.is_active(at(2024, 1, 7, 23, 30)));
+        // 금요일 밤에서 이어진 토요일 새벽도 포함하지 않음
+        assert!(!schedule.is_active(at(2024, 1, 6, 1, 0)));
+    }
+
+    #[test]
+    fn range_without_times_is_whole_day() {
+        let schedule = window("days = [\"sun\"]").schedule().unwrap().clone();
+        assert!(schedule.is_active(at(2024, 1, 7, 0, 0)));
+        assert!(schedule.is_active(at(2024, 1, 7, 23, 59)));
+        assert!(!schedule.is_active(at(2024, 1, 8, 0, 0)));
+    }
+
+    #[test]
+    fn invalid_schedules_are_rejected() {
+        for toml in [
+            "",
+            "cron = \"0 3 * * *\"",
+            "cron = \"0 3 * * *\"\nduration_min = 0",
+            "cron = \"0 3 * * *\"\nduration_min = 60\ndays = [\"sun\"]",
+            "days = [\"someday\"]",
+            "start = \"23:00\"",
+            "start = \"25:00\"\nend = \"02:00\"",
+        ] {
+            let window = window(toml);
+            assert!(window.schedule().is_err(), "{:?}", toml);
+            assert!(!window.is_active(&Local::now()), "{:?}", toml);
+        }
+    }
+}
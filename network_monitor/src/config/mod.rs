@@ -1,9 +1,35 @@
-use serde::{Deserialize, Serialize, de::Error as SerdeError};
+pub mod maintenance;
+
+use crate::network::template;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as SerdeError};
+use std::collections::BTreeMap;
+use std::fmt;
 use std::fs;
+use std::net::IpAddr;
 use std::path::Path;
 use std::time::Duration;
 use thiserror::Error;
 
+pub use maintenance::MaintenanceWindow;
+
+const DEFAULT_PACKETS: u32 = 1;
+const DEFAULT_PACKET_INTERVAL_MS: u64 = 1000;
+const DEFAULT_MAX_CONCURRENT_PROBES: usize = 16;
+const DEFAULT_FAIL_THRESHOLD: u32 = 3;
+const DEFAULT_RECOVER_THRESHOLD: u32 = 2;
+const DEFAULT_FLAP_THRESHOLD: u32 = 5;
+const DEFAULT_FLAP_WINDOW_SEC: u64 = 300;
+const DEFAULT_RECOVERY_COOLDOWN_SEC: u64 = 60;
+const DEFAULT_RECOVERY_BACKOFF: f64 = 2.0;
+const DEFAULT_RECOVERY_ATTEMPTS_PER_HOUR: u32 = 3;
+const DEFAULT_VERIFY_INTERVAL_MS: u64 = 2000;
+const DEFAULT_RECOVERY_TIMEOUT_MS: u64 = 60_000;
+const DEFAULT_RECOVERY_OUTPUT_BYTES: usize = 4096;
+const DEFAULT_NOTIFICATION_TIMEOUT_MS: u64 = 60_000;
+/// 자동 검색된 대상에 붙는 태그 (복구 그룹의 tags로 명시적으로 선택할 때 사용)
+const DISCOVERED_TAG: &str = "discovered";
+
 #[derive(Error, Debug)]
 pub enum ConfigError {
     #[error("설정 파일을 읽을 수 없음: {0}")]
@@ -11,6 +37,181 @@ pub enum ConfigError {
     
     #[error("설정 파일 파싱 오류: {0}")]
     ParseError(#[from] toml::de::Error),
+
+    #[error("대상 '{target}'의 depends_on에 지정한 대상 '{dependency}'이(가) 없음")]
+    UnknownDependency { target: String, dependency: String },
+
+    #[error("대상 의존 관계에 순환이 있음: {0}")]
+    DependencyCycle(String),
+
+    #[error("복구 그룹 '{group}'에 지정한 대상 '{target}'이(가) 없음")]
+    UnknownGroupTarget { group: String, target: String },
+
+    #[error("복구 작업 '{action}'의 verify에 지정한 대상 '{target}'이(가) 없음")]
+    UnknownVerifyTarget { action: String, target: String },
+
+    #[error("'{name}'의 args는 shell = \"{shell}\"에서 사용할 수 없음 (exec, sh, bash만 지원)")]
+    UnsupportedShellArgs { name: String, shell: Shell },
+
+    #[error("'{name}'의 명령 템플릿 오류: {reason}")]
+    InvalidCommandTemplate { name: String, reason: String },
+
+    #[error("점검 시간 '{window}'에 지정한 대상 '{target}'이(가) 없음")]
+    UnknownMaintenanceTarget { window: String, target: String },
+
+    #[error("점검 시간 '{window}' 설정 오류: {reason}")]
+    InvalidMaintenanceWindow { window: String, reason: String },
+}
+
+/// 호스트 이름을 해석할 때 사용할 주소 패밀리입니다.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum AddressFamily {
+    /// IPv4 우선, IPv4 주소가 없으면 IPv6 사용
+    #[default]
+    V4,
+    /// IPv6 우선, IPv6 주소가 없으면 IPv4 사용
+    V6,
+    /// IPv4와 IPv6를 모두 확인하고 각각 보고
+    Both,
+}
+
+/// 명령어를 실행하는 방식입니다. 지정하지 않으면 Windows는 PowerShell, 그 외에는 sh를 사용합니다.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Shell {
+    /// `sh -c` (args는 위치 매개변수 $1, $2, ...로 전달)
+    Sh,
+    /// `bash -c` (args는 위치 매개변수 $1, $2, ...로 전달)
+    Bash,
+    /// `powershell -Command` (Windows 외에는 `pwsh`)
+    Powershell,
+    /// `cmd /C`
+    Cmd,
+    /// 셸 없이 command를 프로그램으로, args를 인수로 그대로 실행
+    Exec,
+}
+
+impl Default for Shell {
+    #[cfg(windows)]
+    fn default() -> Self {
+        Shell::Powershell
+    }
+
+    #[cfg(not(windows))]
+    fn default() -> Self {
+        Shell::Sh
+    }
+}
+
+impl Shell {
+    /// `args`를 전달할 수 있는지 여부
+    pub fn supports_args(self) -> bool {
+        matches!(self, Shell::Sh | Shell::Bash | Shell::Exec)
+    }
+}
+
+impl fmt::Display for Shell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Shell::Sh => "sh",
+            Shell::Bash => "bash",
+            Shell::Powershell => "powershell",
+            Shell::Cmd => "cmd",
+            Shell::Exec => "exec",
+        })
+    }
+}
+
+/// 대상의 상태를 확인하는 방식입니다. 지정하지 않으면 ICMP 핑을 사용합니다.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Probe {
+    Icmp,
+    Http(HttpProbe),
+    Dns(DnsProbe),
+    Tls(TlsProbe),
+    Banner(BannerProbe),
+}
+
+/// HTTP/HTTPS 상태 확인 설정입니다.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HttpProbe {
+    /// 요청 URL (미지정 시 `http://<address>/`)
+    pub url: Option<String>,
+    /// HTTP 메서드 (기본값 GET)
+    pub method: Option<String>,
+    /// 정상으로 간주할 상태 코드 목록 (비어 있으면 2xx)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expected_status: Vec<u16>,
+    /// 응답 본문에 포함되어야 하는 문자열
+    pub body_contains: Option<String>,
+    /// 응답 본문이 일치해야 하는 정규식
+    pub body_regex: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// TLS 인증서 검증 여부 (기본값 true)
+    #[serde(default = "default_true")]
+    pub verify_tls: bool,
+}
+
+/// DNS 조회 확인 설정입니다.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DnsProbe {
+    /// 조회할 도메인 이름
+    pub name: String,
+    /// 레코드 타입 (기본값 A)
+    pub record_type: Option<String>,
+    /// 질의를 보낼 DNS 서버 주소 (`1.1.1.1`, `1.1.1.1:53` 등, 미지정 시 시스템 DNS 서버)
+    pub resolver: Option<String>,
+    /// 응답에 포함되어야 하는 값 목록 (예: IP 주소, CNAME 대상 - CNAME 응답은 레코드 타입과 관계없이 비교)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expected: Vec<String>,
+    /// 정상으로 간주할 RCODE (기본값 NOERROR, `ANY`이면 응답만 확인)
+    pub expected_rcode: Option<String>,
+}
+
+/// TLS 인증서 만료/유효성 확인 설정입니다.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TlsProbe {
+    /// 연결 포트 (미지정 시 대상의 port, 그것도 없으면 443)
+    pub port: Option<u16>,
+    /// SNI 및 인증서 검증에 사용할 서버 이름 (미지정 시 address)
+    pub server_name: Option<String>,
+    /// 만료까지 남은 일수가 이 값 이하이면 경고 상태 (기본값 14)
+    pub warning_days: Option<i64>,
+}
+
+/// 배너 확인에 사용할 전송 프로토콜입니다.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TransportProtocol {
+    #[default]
+    Tcp,
+    Udp,
+}
+
+/// TCP/UDP로 데이터를 보내고 응답을 확인하는 설정입니다 (SMTP/FTP/SSH 배너, Redis PING 등).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BannerProbe {
+    #[serde(default)]
+    pub protocol: TransportProtocol,
+    /// 연결 포트 (미지정 시 대상의 port)
+    pub port: Option<u16>,
+    /// 전송할 텍스트 데이터
+    pub send: Option<String>,
+    /// 전송할 16진수 데이터 (예: "0d0a", "de ad be ef")
+    pub send_hex: Option<String>,
+    /// 응답이 이 텍스트로 시작해야 함
+    pub expect_prefix: Option<String>,
+    /// 응답이 이 16진수 바이트로 시작해야 함
+    pub expect_prefix_hex: Option<String>,
+    /// 응답(UTF-8로 해석)이 일치해야 하는 정규식
+    pub expect_regex: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -20,13 +221,248 @@ pub struct NetworkTarget {
     pub port: Option<u16>,
     pub timeout_ms: Option<u64>,
     pub retry_count: Option<u8>,
+    /// 한 번의 확인에서 보낼 ICMP 에코 요청 수
+    pub packets: Option<u32>,
+    /// 에코 요청 사이의 간격 (밀리초)
+    pub packet_interval_ms: Option<u64>,
+    /// 호스트 이름 해석 시 주소 패밀리 (미지정 시 전역 설정 사용)
+    pub address_family: Option<AddressFamily>,
+    /// 확인 방식 (미지정 시 ICMP)
+    pub probe: Option<Probe>,
+    /// 이 대상의 확인 주기 (초, 미지정 시 전역 check_interval_sec 사용)
+    pub check_interval_sec: Option<u64>,
+    /// 확인 시각을 흩어 놓을 지터 (확인 주기 대비 %, 미지정 시 전역 설정 사용)
+    pub jitter_percent: Option<u8>,
+    /// Down으로 판단할 연속 실패 횟수 (미지정 시 전역 설정 사용)
+    pub fail_threshold: Option<u32>,
+    /// Down 이후 Up으로 판단할 연속 성공 횟수 (미지정 시 전역 설정 사용)
+    pub recover_threshold: Option<u32>,
+    /// 플래핑으로 판단할 감시 구간 내 상태 변경 횟수 (미지정 시 전역 설정 사용)
+    pub flap_threshold: Option<u32>,
+    /// 플래핑 감시 구간 (초, 미지정 시 전역 설정 사용)
+    pub flap_window_sec: Option<u64>,
+    /// 이 대상이 의존하는 상위 대상 이름 (상위 대상이 down이면 확인하지 않고 unreachable로 보고)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    /// 복구 그룹의 대상 선택에 사용하는 태그
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// weighted 장애 판단 정책에서 사용하는 가중치 (기본값 1.0)
+    pub weight: Option<f64>,
+    /// 기본 게이트웨이/DNS 서버 검색으로 자동 생성된 대상 (설정 파일에 저장되지 않음)
+    #[serde(skip)]
+    pub auto_discovered: bool,
+}
+
+/// 복구 그룹이 장애로 판단하는 기준입니다.
+/// 상위 대상의 장애로 unreachable인 대상은 down으로 세지 않으며, 그룹 안에 down 대상이 하나 이상 있어야 합니다.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OutagePolicy {
+    /// 그룹의 모든 대상이 down 또는 unreachable
+    #[default]
+    All,
+    /// 그룹 대상 중 min_down개 이상이 down
+    Quorum { min_down: usize },
+    /// 그룹 대상 중 하나라도 down
+    Any,
+    /// down인 대상의 weight 합이 min_score 이상
+    Weighted { min_score: f64 },
+}
+
+/// 장애 판단 정책과 장애일 때 실행할 복구 작업 묶음입니다.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecoveryGroup {
+    pub name: String,
+    #[serde(default)]
+    pub policy: OutagePolicy,
+    /// 정책에 포함할 대상 이름 (tags와 모두 비어 있으면 자동 검색된 대상을 제외한 모든 대상)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<String>,
+    /// 정책에 포함할 대상 태그
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    pub actions: Vec<RecoveryAction>,
+}
+
+impl RecoveryGroup {
+    /// 대상이 이 그룹의 정책에 포함되는지 여부.
+    /// 자동 검색된 대상은 검색 여부에 따라 장애 판단이 달라지지 않도록 암시적인 전체 그룹에서 제외하며,
+    /// `discovered` 태그나 이름으로 지정해야 포함됩니다.
+    pub fn includes(&self, target: &NetworkTarget) -> bool {
+        if self.targets.is_empty() && self.tags.is_empty() {
+            return !target.auto_discovered;
+        }
+        self.targets.contains(&target.name) || target.tags.iter().any(|tag| self.tags.contains(tag))
+    }
+
+    /// 복구 작업을 severity 오름차순(가벼운 작업부터)으로 정렬한 단계 목록.
+    /// severity가 없으면 설정 파일의 순서를 사용합니다.
+    pub fn ladder(&self) -> Vec<&RecoveryAction> {
+        let mut ladder: Vec<(u32, &RecoveryAction)> = self
+            .actions
+            .iter()
+            .enumerate()
+            .map(|(index, action)| (action.severity.unwrap_or(index as u32), action))
+            .collect();
+        ladder.sort_by_key(|(severity, _)| *severity);
+        ladder.into_iter().map(|(_, action)| action).collect()
+    }
+}
+
+/// 복구 작업의 종류와 매개변수입니다. `kind`를 지정하지 않으면 `command`로 간주합니다.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case", remote = "Self")]
+pub enum RecoveryKind {
+    /// 네트워크 인터페이스를 껐다가 다시 켬
+    RestartInterface { interface: String },
+    /// DNS 캐시 초기화
+    FlushDns,
+    /// DHCP 임대 갱신 (인터페이스를 지정하지 않으면 모든 인터페이스)
+    RenewDhcp {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        interface: Option<String>,
+    },
+    /// 시스템 서비스 재시작 (Windows 서비스 또는 systemd 유닛)
+    RestartService { service: String },
+    /// 명령어 실행 (shell 설정에 따라 실행, exec이면 command가 프로그램이고 args가 인수)
+    Command {
+        command: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        args: Vec<String>,
+    },
+}
+
+impl Serialize for RecoveryKind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RecoveryKind::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RecoveryKind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // kind가 없는 기존 설정(command만 지정)은 명령어 실행으로 처리
+        let mut table = toml::Table::deserialize(deserializer)?;
+        table
+            .entry("kind")
+            .or_insert_with(|| toml::Value::String("command".to_string()));
+        RecoveryKind::deserialize(toml::Value::Table(table)).map_err(D::Error::custom)
+    }
+}
+
+impl fmt::Display for RecoveryKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecoveryKind::RestartInterface { interface } => write!(f, "restart_interface {}", interface),
+            RecoveryKind::FlushDns => f.write_str("flush_dns"),
+            RecoveryKind::RenewDhcp { interface: Some(interface) } => write!(f, "renew_dhcp {}", interface),
+            RecoveryKind::RenewDhcp { interface: None } => f.write_str("renew_dhcp"),
+            RecoveryKind::RestartService { service } => write!(f, "restart_service {}", service),
+            RecoveryKind::Command { command, args } => {
+                f.write_str(command)?;
+                for arg in args {
+                    write!(f, " {}", arg)?;
+                }
+                Ok(())
+            }
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RecoveryAction {
     pub name: String,
-    pub command: String,
+    #[serde(flatten)]
+    pub kind: RecoveryKind,
+    /// command 종류의 실행 방식 (미지정 시 전역 shell)
+    pub shell: Option<Shell>,
     pub wait_after_ms: Option<u64>,
+    /// 복구 단계 순서 (작을수록 먼저, 미지정 시 설정 파일의 순서)
+    pub severity: Option<u32>,
+    /// 장애가 이 시간(초) 이상 계속되어야 실행 (기본값 0)
+    pub min_failure_sec: Option<u64>,
+    /// 같은 장애 중 다시 실행하기까지 기다릴 시간 (초, 기본값 60)
+    pub cooldown_sec: Option<u64>,
+    /// 다시 실행할 때마다 cooldown에 곱할 값 (기본값 2.0)
+    pub backoff_multiplier: Option<f64>,
+    /// 최근 1시간 동안 실행할 수 있는 최대 횟수 (기본값 3)
+    pub max_attempts_per_hour: Option<u32>,
+    /// 실행하지 않고 실행할 명령만 기록 (기본값 false, `--dry-run`이면 모든 작업에 적용)
+    pub dry_run: Option<bool>,
+    /// 명령 하나의 최대 실행 시간 (밀리초, 기본값 60000). 초과하면 하위 프로세스까지 종료
+    pub timeout_ms: Option<u64>,
+    /// 성공으로 볼 종료 코드 (비워 두면 0)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub accepted_exit_codes: Vec<i32>,
+    /// 보관할 표준 출력/오류의 최대 크기 (바이트, 기본값 4096)
+    pub max_output_bytes: Option<usize>,
+    /// 복구 성공 여부를 확인하는 방법 (미지정 시 default_target으로 핑 한 번)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verify: Option<RecoveryVerify>,
+}
+
+impl RecoveryAction {
+    pub fn get_min_failure_duration(&self) -> Duration {
+        Duration::from_secs(self.min_failure_sec.unwrap_or(0))
+    }
+
+    /// `runs`번 실행한 뒤의 cooldown (지수 백오프 적용, 백오프로 늘어나는 값은 최대 1시간)
+    pub fn get_cooldown(&self, runs: u32) -> Duration {
+        let base = self.cooldown_sec.unwrap_or(DEFAULT_RECOVERY_COOLDOWN_SEC) as f64;
+        let multiplier = self.backoff_multiplier.unwrap_or(DEFAULT_RECOVERY_BACKOFF).max(1.0);
+        let exponent = runs.saturating_sub(1).min(32) as i32;
+        Duration::from_secs_f64((base * multiplier.powi(exponent)).min(base.max(3600.0)))
+    }
+
+    pub fn get_max_attempts_per_hour(&self) -> u32 {
+        self.max_attempts_per_hour.unwrap_or(DEFAULT_RECOVERY_ATTEMPTS_PER_HOUR)
+    }
+
+    pub fn get_timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms.unwrap_or(DEFAULT_RECOVERY_TIMEOUT_MS).max(1))
+    }
+
+    pub fn get_max_output_bytes(&self) -> usize {
+        self.max_output_bytes.unwrap_or(DEFAULT_RECOVERY_OUTPUT_BYTES)
+    }
+
+    /// 이 작업을 모의 실행할지 여부 (작업 설정 또는 `--dry-run`)
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run.unwrap_or(false) || crate::utils::is_dry_run_mode()
+    }
+}
+
+/// 복구 작업 후 연결이 복구되었는지 확인하는 방법입니다.
+/// `targets`와 `checks`가 모두 비어 있으면 default_target으로 ICMP 핑을 보냅니다.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RecoveryVerify {
+    /// 확인할 대상 이름 (각 대상에 설정된 probe 사용)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<String>,
+    /// 확인에만 사용할 대상 (모니터링 대상과 같은 형식, 모든 probe 사용 가능)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<NetworkTarget>,
+    /// 성공해야 하는 대상 수 (기본값: 전부)
+    pub min_success: Option<usize>,
+    /// 확인에 성공할 때까지 재시도할 시간 (초, 기본값 0: 한 번만 확인)
+    pub window_sec: Option<u64>,
+    /// 재시도 간격 (밀리초, 기본값 2000)
+    pub retry_interval_ms: Option<u64>,
+}
+
+impl RecoveryVerify {
+    /// 성공해야 하는 대상 수 (1 이상, 확인 대상 수 이하)
+    pub fn get_min_success(&self, total: usize) -> usize {
+        self.min_success.unwrap_or(total).clamp(1, total.max(1))
+    }
+
+    pub fn get_window(&self) -> Duration {
+        Duration::from_secs(self.window_sec.unwrap_or(0))
+    }
+
+    pub fn get_retry_interval(&self) -> Duration {
+        Duration::from_millis(self.retry_interval_ms.unwrap_or(DEFAULT_VERIFY_INTERVAL_MS).max(100))
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -36,10 +472,53 @@ pub struct Config {
     pub ping_timeout_ms: u64,
     pub retry_count: u8,
     pub targets: Vec<NetworkTarget>,
+    /// 모든 대상이 down일 때 실행할 복구 작업 ("default" 그룹)
     pub recovery_actions: Vec<RecoveryAction>,
+    /// 장애 판단 정책별 복구 작업 그룹
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recovery_groups: Vec<RecoveryGroup>,
+    /// 복구 작업과 알림을 멈추는 점검 시간
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub maintenance_windows: Vec<MaintenanceWindow>,
     pub log_file: Option<String>,
     pub notification_enabled: bool,
     pub notification_command: Option<String>,
+    /// 알림 명령에 전달할 인수 (shell이 exec이면 프로그램 인수, sh/bash이면 위치 매개변수)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notification_args: Vec<String>,
+    /// 알림 명령의 최대 실행 시간 (밀리초, 기본값 60000). 초과하면 하위 프로세스까지 종료
+    pub notification_timeout_ms: Option<u64>,
+    /// 알림 명령과 command 복구 작업의 기본 실행 방식 (미지정 시 Windows는 powershell, 그 외에는 sh)
+    pub shell: Option<Shell>,
+    #[serde(default)]
+    pub address_family: AddressFamily,
+    /// 기본 게이트웨이와 DNS 서버를 찾아 대상으로 자동 추가할지 여부 (기본값 true)
+    #[serde(default = "default_true")]
+    pub auto_discover: bool,
+    /// 한 확인 주기에서 동시에 확인할 최대 대상 수 (기본값 16)
+    pub max_concurrent_probes: Option<usize>,
+    /// 대상별 확인 시각에 더할 임의 지연의 최대값 (확인 주기 대비 %, 기본값 0)
+    pub jitter_percent: Option<u8>,
+    /// Down으로 판단할 연속 실패 횟수 (기본값 3)
+    pub fail_threshold: Option<u32>,
+    /// Down 이후 Up으로 판단할 연속 성공 횟수 (기본값 2)
+    pub recover_threshold: Option<u32>,
+    /// 플래핑으로 판단할 감시 구간 내 상태 변경 횟수 (기본값 5, 0이면 감지 안 함)
+    pub flap_threshold: Option<u32>,
+    /// 플래핑 감시 구간 (초, 기본값 300)
+    pub flap_window_sec: Option<u64>,
+}
+
+/// 기본 알림 명령. 기본 셸(Windows는 powershell, 그 외에는 sh)로 실행되며 템플릿 변수로 대상과 상태를 넣습니다.
+#[cfg(windows)]
+fn default_notification_command() -> &'static str {
+    "[System.Reflection.Assembly]::LoadWithPartialName('System.Windows.Forms') | Out-Null; [System.Windows.Forms.MessageBox]::Show('네트워크 상태 변경: ' + {target.name} + ' → ' + {event}, '네트워크 모니터', [System.Windows.Forms.MessageBoxButtons]::OK, [System.Windows.Forms.MessageBoxIcon]::Information)"
+}
+
+/// 기본 알림 명령. 기본 셸(Windows는 powershell, 그 외에는 sh)로 실행되며 템플릿 변수로 대상과 상태를 넣습니다.
+#[cfg(not(windows))]
+fn default_notification_command() -> &'static str {
+    "logger -t network_monitor -- 네트워크 상태 변경: {target.name} {event}"
 }
 
 impl Default for Config {
@@ -56,6 +535,20 @@ impl Default for Config {
                     port: None,
                     timeout_ms: Some(1000),
                     retry_count: Some(3),
+                    packets: None,
+                    packet_interval_ms: None,
+                    address_family: None,
+                    probe: None,
+                    check_interval_sec: None,
+                    jitter_percent: None,
+                    fail_threshold: None,
+                    recover_threshold: None,
+                    flap_threshold: None,
+                    flap_window_sec: None,
+                    depends_on: Vec::new(),
+                    tags: Vec::new(),
+                    weight: None,
+                    auto_discovered: false,
                 },
                 NetworkTarget {
                     name: "Local Router".to_string(),
@@ -63,23 +556,76 @@ impl Default for Config {
                     port: None,
                     timeout_ms: Some(500),
                     retry_count: Some(2),
+                    packets: None,
+                    packet_interval_ms: None,
+                    address_family: None,
+                    probe: None,
+                    check_interval_sec: None,
+                    jitter_percent: None,
+                    fail_threshold: None,
+                    recover_threshold: None,
+                    flap_threshold: None,
+                    flap_window_sec: None,
+                    depends_on: Vec::new(),
+                    tags: Vec::new(),
+                    weight: None,
+                    auto_discovered: false,
                 },
             ],
             recovery_actions: vec![
                 RecoveryAction {
                     name: "네트워크 어댑터 재시작".to_string(),
-                    command: "powershell -Command \"Restart-NetAdapter -Name 'Ethernet' -Confirm:$false\"".to_string(),
+                    // 기본 경로가 사용하는 인터페이스를 실행할 때 찾아서 사용
+                    kind: RecoveryKind::RestartInterface {
+                        interface: "{interface}".to_string(),
+                    },
+                    shell: None,
                     wait_after_ms: Some(5000),
+                    severity: None,
+                    min_failure_sec: None,
+                    cooldown_sec: None,
+                    backoff_multiplier: None,
+                    max_attempts_per_hour: None,
+                    dry_run: None,
+                    timeout_ms: None,
+                    accepted_exit_codes: Vec::new(),
+                    max_output_bytes: None,
+                    verify: None,
                 },
             ],
+            recovery_groups: Vec::new(),
+            maintenance_windows: Vec::new(),
             log_file: Some("network_monitor.log".to_string()),
             notification_enabled: true,
-            notification_command: Some("powershell -Command \"[System.Reflection.Assembly]::LoadWithPartialName('System.Windows.Forms'); [System.Windows.Forms.MessageBox]::Show('네트워크 연결이 복구되었습니다.', '네트워크 모니터', [System.Windows.Forms.MessageBoxButtons]::OK, [System.Windows.Forms.MessageBoxIcon]::Information)\"".to_string()),
+            notification_command: Some(default_notification_command().to_string()),
+            notification_args: Vec::new(),
+            notification_timeout_ms: None,
+            shell: None,
+            address_family: AddressFamily::default(),
+            auto_discover: true,
+            max_concurrent_probes: None,
+            jitter_percent: None,
+            fail_threshold: None,
+            recover_threshold: None,
+            flap_threshold: None,
+            flap_window_sec: None,
         }
     }
 }
 
 impl Config {
+    pub fn get_shell(&self) -> Shell {
+        self.shell.unwrap_or_default()
+    }
+
+    pub fn get_action_shell(&self, action: &RecoveryAction) -> Shell {
+        action.shell.unwrap_or_else(|| self.get_shell())
+    }
+
+    pub fn get_notification_timeout(&self) -> Duration {
+        Duration::from_millis(self.notification_timeout_ms.unwrap_or(DEFAULT_NOTIFICATION_TIMEOUT_MS).max(1))
+    }
+
     pub fn get_target_timeout(&self, target: &NetworkTarget) -> Duration {
         Duration::from_millis(target.timeout_ms.unwrap_or(self.ping_timeout_ms))
     }
@@ -87,6 +633,275 @@ impl Config {
     pub fn get_target_retry_count(&self, target: &NetworkTarget) -> u8 {
         target.retry_count.unwrap_or(self.retry_count)
     }
+
+    pub fn get_target_packets(&self, target: &NetworkTarget) -> u32 {
+        target.packets.unwrap_or(DEFAULT_PACKETS).max(1)
+    }
+
+    pub fn get_target_address_family(&self, target: &NetworkTarget) -> AddressFamily {
+        target.address_family.unwrap_or(self.address_family)
+    }
+
+    pub fn get_target_packet_interval(&self, target: &NetworkTarget) -> Duration {
+        Duration::from_millis(target.packet_interval_ms.unwrap_or(DEFAULT_PACKET_INTERVAL_MS))
+    }
+
+    pub fn get_target_check_interval(&self, target: &NetworkTarget) -> Duration {
+        Duration::from_secs(target.check_interval_sec.unwrap_or(self.check_interval_sec).max(1))
+    }
+
+    /// 확인 시각에 더할 임의 지연의 최대값 (확인 주기의 jitter_percent%, 최대 100%)
+    pub fn get_target_jitter(&self, target: &NetworkTarget) -> Duration {
+        let percent = target.jitter_percent.or(self.jitter_percent).unwrap_or(0).min(100);
+        self.get_target_check_interval(target) * u32::from(percent) / 100
+    }
+
+    pub fn get_target_fail_threshold(&self, target: &NetworkTarget) -> u32 {
+        target.fail_threshold.or(self.fail_threshold).unwrap_or(DEFAULT_FAIL_THRESHOLD).max(1)
+    }
+
+    pub fn get_target_recover_threshold(&self, target: &NetworkTarget) -> u32 {
+        target.recover_threshold.or(self.recover_threshold).unwrap_or(DEFAULT_RECOVER_THRESHOLD).max(1)
+    }
+
+    pub fn get_target_flap_threshold(&self, target: &NetworkTarget) -> u32 {
+        target.flap_threshold.or(self.flap_threshold).unwrap_or(DEFAULT_FLAP_THRESHOLD)
+    }
+
+    pub fn get_target_flap_window(&self, target: &NetworkTarget) -> Duration {
+        Duration::from_secs(target.flap_window_sec.or(self.flap_window_sec).unwrap_or(DEFAULT_FLAP_WINDOW_SEC))
+    }
+
+    pub fn get_target_weight(&self, target: &NetworkTarget) -> f64 {
+        target.weight.unwrap_or(1.0)
+    }
+
+    /// 복구 작업 그룹 목록. `recovery_actions`는 (자동 검색 대상을 제외한) 모든 대상이 down일 때 실행하는 "default" 그룹이 됩니다.
+    pub fn get_recovery_groups(&self) -> Vec<RecoveryGroup> {
+        let mut groups = Vec::with_capacity(self.recovery_groups.len() + 1);
+        if !self.recovery_actions.is_empty() {
+            groups.push(RecoveryGroup {
+                name: "default".to_string(),
+                policy: OutagePolicy::All,
+                targets: Vec::new(),
+                tags: Vec::new(),
+                actions: self.recovery_actions.clone(),
+            });
+        }
+        groups.extend(self.recovery_groups.iter().cloned());
+        groups
+    }
+
+    /// `now`에 대상이 들어 있는 점검 시간. 여러 개이면 설정 파일에서 먼저 나온 것
+    pub fn active_maintenance(&self, target: &NetworkTarget, now: &DateTime<Local>) -> Option<&MaintenanceWindow> {
+        self.maintenance_windows
+            .iter()
+            .find(|window| window.includes(target) && window.is_active(now))
+    }
+
+    pub fn get_max_concurrent_probes(&self) -> usize {
+        self.max_concurrent_probes.unwrap_or(DEFAULT_MAX_CONCURRENT_PROBES).max(1)
+    }
+
+    /// 검색된 기본 게이트웨이(ICMP)와 DNS 서버(루트 NS 질의 응답 여부)로 자동 대상을 다시 만듭니다.
+    /// 사용자가 이미 같은 주소를 대상으로 지정했으면 추가하지 않습니다.
+    /// 자동 대상 목록이 바뀌었으면 true를 반환합니다.
+    pub fn apply_discovered_targets(&mut self, gateways: &[IpAddr], dns_servers: &[IpAddr]) -> bool {
+        let manual: Vec<&NetworkTarget> = self.targets.iter().filter(|t| !t.auto_discovered).collect();
+        let mut discovered = Vec::new();
+
+        for gateway in gateways {
+            let address = gateway.to_string();
+            if manual.iter().any(|t| t.address == address && matches!(t.probe, None | Some(Probe::Icmp))) {
+                continue;
+            }
+            discovered.push(discovered_target(format!("Default Gateway ({})", address), address, None));
+        }
+        for server in dns_servers {
+            let address = server.to_string();
+            let configured = manual.iter().any(|t| match &t.probe {
+                Some(Probe::Dns(probe)) => probe.resolver.as_deref() == Some(address.as_str()),
+                _ => false,
+            });
+            if configured {
+                continue;
+            }
+            let probe = Probe::Dns(DnsProbe {
+                name: ".".to_string(),
+                record_type: Some("NS".to_string()),
+                resolver: Some(address.clone()),
+                expected: Vec::new(),
+                expected_rcode: Some("ANY".to_string()),
+            });
+            discovered.push(discovered_target(format!("DNS Server ({})", address), address, Some(probe)));
+        }
+
+        let current: Vec<(&str, &str)> = self
+            .targets
+            .iter()
+            .filter(|t| t.auto_discovered)
+            .map(|t| (t.name.as_str(), t.address.as_str()))
+            .collect();
+        let next: Vec<(&str, &str)> = discovered.iter().map(|t| (t.name.as_str(), t.address.as_str())).collect();
+        if current == next {
+            return false;
+        }
+
+        self.targets.retain(|t| !t.auto_discovered);
+        self.targets.extend(discovered);
+        true
+    }
+
+    /// 대상의 `depends_on`, 복구 그룹의 `targets`, 복구 작업 verify의 `targets`가 존재하는 대상을 가리키는지,
+    /// 명령 인수(args)를 지원하는 shell인지, 점검 시간의 일정과 대상이 올바른지,
+    /// 의존 관계에 순환이 없는지(DAG인지) 검사합니다.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        for target in &self.targets {
+            for dependency in &target.depends_on {
+                if !self.targets.iter().any(|t| t.name == *dependency) {
+                    return Err(ConfigError::UnknownDependency {
+                        target: target.name.clone(),
+                        dependency: dependency.clone(),
+                    });
+                }
+            }
+        }
+
+        for group in &self.recovery_groups {
+            for name in &group.targets {
+                if !self.targets.iter().any(|t| t.name == *name) {
+                    return Err(ConfigError::UnknownGroupTarget {
+                        group: group.name.clone(),
+                        target: name.clone(),
+                    });
+                }
+            }
+        }
+
+        if !self.notification_args.is_empty() && !self.get_shell().supports_args() {
+            return Err(ConfigError::UnsupportedShellArgs {
+                name: "notification_command".to_string(),
+                shell: self.get_shell(),
+            });
+        }
+        if let Some(command) = &self.notification_command {
+            template::check_command(self.get_shell(), command).map_err(|e| ConfigError::InvalidCommandTemplate {
+                name: "notification_command".to_string(),
+                reason: e.to_string(),
+            })?;
+        }
+
+        for group in self.get_recovery_groups() {
+            for action in &group.actions {
+                let shell = self.get_action_shell(action);
+                if matches!(&action.kind, RecoveryKind::Command { args, .. } if !args.is_empty()) && !shell.supports_args() {
+                    return Err(ConfigError::UnsupportedShellArgs {
+                        name: action.name.clone(),
+                        shell,
+                    });
+                }
+                if let RecoveryKind::Command { command, .. } = &action.kind {
+                    template::check_command(shell, command).map_err(|e| ConfigError::InvalidCommandTemplate {
+                        name: action.name.clone(),
+                        reason: e.to_string(),
+                    })?;
+                }
+
+                let Some(verify) = &action.verify else {
+                    continue;
+                };
+                for name in &verify.targets {
+                    if !self.targets.iter().any(|t| t.name == *name) {
+                        return Err(ConfigError::UnknownVerifyTarget {
+                            action: action.name.clone(),
+                            target: name.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        for window in &self.maintenance_windows {
+            if let Err(reason) = window.schedule() {
+                return Err(ConfigError::InvalidMaintenanceWindow {
+                    window: window.name.clone(),
+                    reason,
+                });
+            }
+            for name in &window.targets {
+                if !self.targets.iter().any(|t| t.name == *name) {
+                    return Err(ConfigError::UnknownMaintenanceTarget {
+                        window: window.name.clone(),
+                        target: name.clone(),
+                    });
+                }
+            }
+        }
+
+        // 깊이 우선 탐색으로 현재 경로에 다시 나타나는 대상을 찾음
+        fn visit<'a>(
+            config: &'a Config,
+            name: &'a str,
+            path: &mut Vec<&'a str>,
+            done: &mut Vec<&'a str>,
+        ) -> Result<(), ConfigError> {
+            if done.contains(&name) {
+                return Ok(());
+            }
+            if let Some(start) = path.iter().position(|n| *n == name) {
+                let mut cycle = path[start..].to_vec();
+                cycle.push(name);
+                return Err(ConfigError::DependencyCycle(cycle.join(" → ")));
+            }
+
+            path.push(name);
+            if let Some(target) = config.targets.iter().find(|t| t.name == name) {
+                for dependency in &target.depends_on {
+                    visit(config, dependency, path, done)?;
+                }
+            }
+            path.pop();
+            done.push(name);
+            Ok(())
+        }
+
+        let mut done = Vec::new();
+        for target in &self.targets {
+            visit(self, &target.name, &mut Vec::new(), &mut done)?;
+        }
+        Ok(())
+    }
+
+    /// 자동 생성된 대상을 제외한, 설정 파일에 저장할 설정을 반환합니다.
+    pub fn without_discovered_targets(&self) -> Config {
+        let mut config = self.clone();
+        config.targets.retain(|t| !t.auto_discovered);
+        config
+    }
+}
+
+fn discovered_target(name: String, address: String, probe: Option<Probe>) -> NetworkTarget {
+    NetworkTarget {
+        name,
+        address,
+        port: None,
+        timeout_ms: None,
+        retry_count: None,
+        packets: None,
+        packet_interval_ms: None,
+        address_family: None,
+        probe,
+        check_interval_sec: None,
+        jitter_percent: None,
+        fail_threshold: None,
+        recover_threshold: None,
+        flap_threshold: None,
+        flap_window_sec: None,
+        depends_on: Vec::new(),
+        tags: vec![DISCOVERED_TAG.to_string()],
+        weight: None,
+        auto_discovered: true,
+    }
 }
 
 pub fn load_config<P: AsRef<Path>>(path: P) -> Result<Config, ConfigError> {
@@ -102,13 +917,100 @@ pub fn load_config<P: AsRef<Path>>(path: P) -> Result<Config, ConfigError> {
     // 설정 파일 읽기
     let config_str = fs::read_to_string(path)?;
     let config: Config = toml::from_str(&config_str)?;
+    config.validate()?;
     
     Ok(config)
 }
 
+#[cfg_attr(not(feature = "gui"), allow(dead_code))]
 pub fn save_config<P: AsRef<Path>>(config: &Config, path: P) -> Result<(), ConfigError> {
-    let toml_string = toml::to_string_pretty(config)
+    config.validate()?;
+    let toml_string = toml::to_string_pretty(&config.without_discovered_targets())
         .map_err(|e| ConfigError::ParseError(toml::de::Error::custom(format!("{}", e))))?;
     fs::write(path, toml_string)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::test_support::target;
+
+    /// 대상 목록만 바꾼 설정 (기본 복구 작업 없음)
+    fn config(targets: Vec<NetworkTarget>) -> Config {
+        Config {
+            targets,
+            recovery_actions: Vec::new(),
+            ..Config::default()
+        }
+    }
+
+    fn group(name: &str, body: &str) -> RecoveryGroup {
+        toml::from_str(&format!("name = \"{}\"\n{}\n[[actions]]\nname = \"restart\"\ncommand = \"true\"\n", name, body))
+            .unwrap()
+    }
+
+    #[test]
+    fn rejects_self_dependency() {
+        let err = config(vec![target("a", &["a"])]).validate().unwrap_err();
+        assert!(matches!(err, ConfigError::DependencyCycle(cycle) if cycle == "a → a"));
+    }
+
+    #[test]
+    fn rejects_three_node_cycle() {
+        let config = config(vec![target("a", &["b"]), target("b", &["c"]), target("c", &["a"])]);
+        let err = config.validate().unwrap_err();
+        assert!(matches!(err, ConfigError::DependencyCycle(cycle) if cycle == "a → b → c → a"));
+    }
+
+    #[test]
+    fn accepts_diamond_dependencies() {
+        let config = config(vec![
+            target("top", &["left", "right"]),
+            target("left", &["bottom"]),
+            target("right", &["bottom"]),
+            target("bottom", &[]),
+        ]);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_unknown_dependency() {
+        let err = config(vec![target("a", &["missing"])]).validate().unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::UnknownDependency { target, dependency } if target == "a" && dependency == "missing"
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_group_target() {
+        let config = Config {
+            recovery_groups: vec![group("lan", "targets = [\"missing\"]")],
+            ..config(vec![target("a", &[])])
+        };
+        let err = config.validate().unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::UnknownGroupTarget { group, target } if group == "lan" && target == "missing"
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_verify_target() {
+        let mut group = group("lan", "");
+        group.actions[0].verify = Some(RecoveryVerify {
+            targets: vec!["missing".to_string()],
+            ..RecoveryVerify::default()
+        });
+        let config = Config {
+            recovery_groups: vec![group],
+            ..config(vec![target("a", &[])])
+        };
+        let err = config.validate().unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::UnknownVerifyTarget { action, target } if action == "restart" && target == "missing"
+        ));
+    }
+}
@@ -1,12 +1,18 @@
-use crate::config::{Config, NetworkTarget};
-use crate::network;
+use crate::config::{Config, MaintenanceWindow, NetworkTarget, OutagePolicy, RecoveryGroup};
+use crate::monitor::{self, HealthEventKind, HealthState, MonitorEvent, MonitorLink, TargetUpdate, Verification};
+use crate::network::{self, stats::as_millis_f64, AddressPing, ConnectionTiming, ExecutionResult, NetworkInterface, PingStats};
+use crate::probe::{self, icmp::primary_stats, ProbeDetail, ProbeReport, ProbeStatus};
 use eframe::{egui, CreationContext};
-use egui::{Color32, RichText, Ui, FontId, FontFamily, TextStyle};
+use egui::{Color32, Ui, FontId, FontFamily, TextStyle};
 use poll_promise::Promise;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tokio::runtime::Runtime;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
 
 // Network target status information
 #[derive(Clone, Debug)]
@@ -14,9 +20,20 @@ pub struct TargetStatus {
     pub name: String,
     pub address: String,
     pub port: Option<u16>,
+    pub kind: &'static str,
+    /// Generated from the discovered default gateway / DNS servers
+    pub auto_discovered: bool,
     pub last_check: Instant,
-    pub ping_result: Option<Result<Duration, String>>,
-    pub port_result: Option<Result<(), String>>,
+    pub probe_result: Option<ProbeReport>,
+    pub port_result: Option<Result<ConnectionTiming, String>>,
+    /// Up/down state after fail/recover thresholds are applied
+    pub health: HealthState,
+    /// State changes too often; per-transition alerts are suppressed
+    pub flapping: bool,
+    /// Down parent target that makes this one unreachable
+    pub root_cause: Option<String>,
+    /// Active maintenance window; recovery and notifications are suppressed
+    pub maintenance: Option<String>,
 }
 
 impl TargetStatus {
@@ -25,15 +42,86 @@ impl TargetStatus {
             name: target.name.clone(),
             address: target.address.clone(),
             port: target.port,
+            kind: probe::probe_kind(target),
+            auto_discovered: target.auto_discovered,
             last_check: Instant::now(),
-            ping_result: None,
+            probe_result: None,
             port_result: None,
+            health: HealthState::Unknown,
+            flapping: false,
+            root_cause: None,
+            maintenance: None,
         }
     }
 
+    /// Take over a result reported by the monitoring loop
+    pub fn apply(&mut self, update: TargetUpdate) {
+        if let Some(check) = update.check {
+            self.last_check = Instant::now();
+            self.probe_result = Some(check.report);
+            self.port_result = check.port;
+        }
+        self.health = update.health;
+        self.flapping = update.flapping;
+        self.root_cause = update.root_cause;
+        self.maintenance = update.maintenance;
+    }
+
     pub fn is_ok(&self) -> bool {
-        self.ping_result.as_ref().map_or(false, |r| r.is_ok())
-            && (self.port.is_none() || self.port_result.as_ref().map_or(false, |r| r.is_ok()))
+        self.probe_result.as_ref().is_some_and(|r| r.is_success())
+            && (self.port.is_none() || self.port_result.as_ref().is_some_and(|r| r.is_ok()))
+    }
+
+    /// Per-address ICMP results (one per family for dual-stack targets)
+    pub fn pings(&self) -> &[AddressPing] {
+        match self.probe_result.as_ref().map(|r| &r.detail) {
+            Some(ProbeDetail::Icmp(results)) => results,
+            _ => &[],
+        }
+    }
+
+    /// Connection phase timing from the probe, falling back to the port check
+    pub fn timing(&self) -> Option<&ConnectionTiming> {
+        match self.probe_result.as_ref().map(|r| &r.detail) {
+            Some(ProbeDetail::Timing(timing)) => Some(timing),
+            _ => self.port_result.as_ref().and_then(|r| r.as_ref().ok()),
+        }
+    }
+}
+
+/// Number of recovery results kept for the status tab
+const MAX_RECOVERY_RECORDS: usize = 50;
+
+// Result of one recovery action, run by the monitoring loop or manually from the GUI
+#[derive(Clone, Debug)]
+pub struct RecoveryRecord {
+    pub time: String,
+    /// Recovery group, or "manual" for runs started from the GUI
+    pub group: String,
+    pub action: String,
+    pub result: ExecutionResult,
+    /// Connectivity check after a successful run
+    pub verification: Option<Verification>,
+}
+
+// Keep a recovery result for the status tab, dropping the oldest beyond MAX_RECOVERY_RECORDS
+fn push_recovery_record(
+    records: &Mutex<Vec<RecoveryRecord>>,
+    group: &str,
+    action: &str,
+    result: ExecutionResult,
+    verification: Option<Verification>,
+) {
+    if let Ok(mut records) = records.lock() {
+        records.push(RecoveryRecord {
+            time: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            group: group.to_string(),
+            action: action.to_string(),
+            result,
+            verification,
+        });
+        let excess = records.len().saturating_sub(MAX_RECOVERY_RECORDS);
+        records.drain(..excess);
     }
 }
 
@@ -45,18 +133,29 @@ pub struct NetworkMonitorApp {
     logs: Vec<(String, Color32)>,
     selected_tab: Tab,
     monitoring_active: bool,
-    monitoring_handle: Option<std::thread::JoinHandle<()>>,
+    /// Shared monitoring loop (`monitor::start_monitoring_with`) running on the runtime
+    monitoring_handle: Option<JoinHandle<()>>,
+    /// Cleared to stop the monitoring loop
+    monitoring_running: Arc<AtomicBool>,
+    /// Check results and state transitions from the monitoring loop, drained into the status grid and log view
+    monitor_events: Option<UnboundedReceiver<MonitorEvent>>,
+    /// Saved settings handed to the running monitoring loop
+    config_updates: Option<watch::Sender<Config>>,
     runtime: Arc<Runtime>,
     recovery_in_progress: bool,
     recovery_promise: Option<Promise<Result<(), String>>>,
+    /// Command results of manual recovery runs, newest last
+    recovery_results: Arc<Mutex<Vec<RecoveryRecord>>>,
     show_config_editor: bool,
     config_editor_text: String,
     config_save_error: Option<String>,
+    interfaces: Option<Result<Vec<NetworkInterface>, String>>,
 }
 
 #[derive(PartialEq)]
 enum Tab {
     Status,
+    Interfaces,
     Settings,
     Logs,
 }
@@ -87,13 +186,14 @@ impl NetworkMonitorApp {
         configure_fonts(&cc.egui_ctx);
 
         // Load configuration
-        let config = match crate::config::load_config(&config_path) {
+        let mut config = match crate::config::load_config(&config_path) {
             Ok(config) => config,
             Err(e) => {
                 eprintln!("Failed to load config: {}", e);
                 Config::default()
             }
         };
+        monitor::refresh_discovered_targets(&mut config);
 
         // Initialize target statuses
         let target_statuses = Arc::new(Mutex::new(HashMap::new()));
@@ -120,12 +220,17 @@ impl NetworkMonitorApp {
             selected_tab: Tab::Status,
             monitoring_active: false,
             monitoring_handle: None,
+            monitoring_running: Arc::new(AtomicBool::new(false)),
+            monitor_events: None,
+            config_updates: None,
             runtime,
             recovery_in_progress: false,
             recovery_promise: None,
+            recovery_results: Arc::new(Mutex::new(Vec::new())),
             show_config_editor: false,
             config_editor_text: String::new(),
             config_save_error: None,
+            interfaces: None,
         }
     }
 
@@ -140,72 +245,40 @@ impl NetworkMonitorApp {
         if self.monitoring_active {
             return;
         }
+        // The previous loop finishes its running recovery actions before it exits
+        if self.monitoring_handle.as_ref().is_some_and(|handle| !handle.is_finished()) {
+            self.add_log("Previous monitoring is still stopping, try again shortly", Color32::YELLOW);
+            return;
+        }
+
+        let config = match self.config.lock() {
+            Ok(config) => Some(config.clone()),
+            Err(_) => None,
+        };
+        let Some(config) = config else {
+            self.add_log("Failed to lock config for monitoring", Color32::RED);
+            return;
+        };
 
         self.monitoring_active = true;
         self.add_log("Monitoring started", Color32::GREEN);
 
-        let config = self.config.clone();
-        let target_statuses = self.target_statuses.clone();
-        let runtime = self.runtime.clone();
-
-        let handle = std::thread::spawn(move || {
-            while let Ok(config_guard) = config.lock() {
-                let check_interval = config_guard.check_interval_sec;
-                let ping_timeout = config_guard.ping_timeout_ms;
-                let targets = config_guard.targets.clone();
-                drop(config_guard); // Release lock before async operations
-
-                for target in targets {
-                    let target_name = target.name.clone();
-                    let target_address = target.address.clone();
-                    let target_port = target.port;
-
-                    // Get or create status
-                    let mut status = {
-                        if let Ok(mut statuses) = target_statuses.lock() {
-                            if let Some(status) = statuses.get_mut(&target_name) {
-                                status.clone()
-                            } else {
-                                let new_status = TargetStatus::new(&target);
-                                statuses.insert(target_name.clone(), new_status.clone());
-                                new_status
-                            }
-                        } else {
-                            continue; // Skip if can't lock
-                        }
-                    };
-
-                    // Update last check time
-                    status.last_check = Instant::now();
-
-                    // Ping check
-                    let ping_result = runtime.block_on(
-                        network::ping_host(&target_address, Duration::from_millis(ping_timeout))
-                    );
-                    // anyhow::Error를 String으로 변환
-                    status.ping_result = Some(ping_result.map_err(|e| e.to_string()));
-
-                    // Port check if specified
-                    if let Some(port) = target_port {
-                        let port_result = runtime.block_on(
-                            network::check_port(&target_address, port, Duration::from_millis(ping_timeout))
-                        );
-                        // anyhow::Error를 String으로 변환
-                        status.port_result = Some(port_result.map_err(|e| e.to_string()));
-                    }
-
-                    // Update status in shared state
-                    if let Ok(mut statuses) = target_statuses.lock() {
-                        statuses.insert(target_name, status);
-                    }
-                }
+        let (events, monitor_events) = mpsc::unbounded_channel();
+        let (config_updates, config_receiver) = watch::channel(config.clone());
+        self.monitoring_running = Arc::new(AtomicBool::new(true));
+        let link = MonitorLink {
+            running: Some(self.monitoring_running.clone()),
+            events: Some(events),
+            config_updates: Some(config_receiver),
+        };
+        self.monitor_events = Some(monitor_events);
+        self.config_updates = Some(config_updates);
 
-                // Sleep for check interval
-                std::thread::sleep(Duration::from_secs(check_interval));
+        self.monitoring_handle = Some(self.runtime.spawn(async move {
+            if let Err(e) = monitor::start_monitoring_with(config, link).await {
+                log::error!("Monitoring failed: {}", e);
             }
-        });
-
-        self.monitoring_handle = Some(handle);
+        }));
     }
 
     // Stop monitoring
@@ -215,9 +288,61 @@ impl NetworkMonitorApp {
         }
 
         self.monitoring_active = false;
+        self.monitoring_running.store(false, Ordering::SeqCst);
+        self.config_updates = None;
         self.add_log("Monitoring stopped", Color32::YELLOW);
-        
-        // Current thread cannot be stopped, but we use a flag to prevent starting new monitoring
+    }
+
+    // Show results from the monitoring loop
+    fn drain_monitor_events(&mut self) {
+        let mut events = Vec::new();
+        if let Some(receiver) = self.monitor_events.as_mut() {
+            while let Ok(event) = receiver.try_recv() {
+                events.push(event);
+            }
+        }
+
+        for event in events {
+            match event {
+                MonitorEvent::Target(update) => {
+                    if let Ok(mut statuses) = self.target_statuses.lock() {
+                        if let Some(status) = statuses.get_mut(&update.target) {
+                            status.apply(*update);
+                        }
+                    }
+                }
+                MonitorEvent::Health(event) => {
+                    let color = match &event.kind {
+                        HealthEventKind::Transition { to, .. } => health_color(*to),
+                        HealthEventKind::Unreachable { .. } => health_color(HealthState::Unreachable),
+                        HealthEventKind::FlappingStarted { .. } => FLAPPING_COLOR,
+                        HealthEventKind::FlappingStopped { state } => health_color(*state),
+                    };
+                    self.add_log(&event.to_string(), color);
+                }
+                MonitorEvent::Recovery { group, action, result, verification } => {
+                    push_recovery_record(&self.recovery_results, &group, &action, result, verification);
+                }
+                MonitorEvent::TargetsChanged(targets) => {
+                    if let Ok(mut config) = self.config.lock() {
+                        config.targets = targets.clone();
+                    }
+                    self.sync_target_statuses(&targets);
+                }
+            }
+        }
+    }
+
+    // Drop statuses of removed targets and add new ones
+    fn sync_target_statuses(&self, targets: &[NetworkTarget]) {
+        if let Ok(mut statuses) = self.target_statuses.lock() {
+            statuses.retain(|name, _| targets.iter().any(|t| t.name == *name));
+            for target in targets {
+                if !statuses.contains_key(&target.name) {
+                    statuses.insert(target.name.clone(), TargetStatus::new(target));
+                }
+            }
+        }
     }
 
     // Execute recovery actions
@@ -231,46 +356,92 @@ impl NetworkMonitorApp {
 
         let config = self.config.clone();
         let logs = Arc::new(Mutex::new(Vec::new()));
+        let recovery_results = self.recovery_results.clone();
         let runtime = self.runtime.clone();
 
         self.recovery_promise = Some(Promise::spawn_thread("recovery", move || {
-            let config_guard = config.lock().unwrap();
-            let config_ref = &*config_guard;
+            // Work on a copy so the UI thread is not blocked during waits and verification
+            let config_copy = match config.lock() {
+                Ok(config) => config.clone(),
+                Err(_) => return Err("Failed to lock config for recovery".to_string()),
+            };
+            let config_ref = &config_copy;
 
-            for action in &config_ref.recovery_actions {
+            // Manual recovery runs every group's actions in order
+            let groups = config_ref.get_recovery_groups();
+            let context = network::TemplateContext {
+                interface: network::default_route_interface(),
+                event: Some("manual".to_string()),
+                ..Default::default()
+            };
+            for action in groups.iter().flat_map(|group| &group.actions) {
+                let plan = match network::RecoveryPlan::new(config_ref, action, &context) {
+                    Ok(plan) => plan,
+                    Err(e) => return Err(format!("Recovery action '{}' could not be built: {}", action.name, e)),
+                };
                 let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+                if action.is_dry_run() {
+                    // Log what would run, without running or verifying it
+                    if let Ok(mut log_vec) = logs.lock() {
+                        log_vec.push((
+                            format!("[{}] [dry-run] Would execute recovery action '{}': {}", timestamp, action.name, plan),
+                            Color32::LIGHT_BLUE
+                        ));
+                    }
+                    continue;
+                }
                 if let Ok(mut log_vec) = logs.lock() {
                     log_vec.push((
-                        format!("[{}] Executing recovery action '{}'", timestamp, action.name),
+                        format!("[{}] Executing recovery action '{}': {}", timestamp, action.name, plan),
                         Color32::YELLOW
                     ));
                 }
 
-                match runtime.block_on(network::execute_command(&action.command)) {
-                    Ok(output) => {
-                        let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
-                        if let Ok(mut log_vec) = logs.lock() {
-                            log_vec.push((
-                                format!("[{}] Recovery action '{}' succeeded: {}", timestamp, action.name, output),
-                                Color32::GREEN
-                            ));
-                        }
+                let result = runtime.block_on(plan.run(action));
+                let record = |verification: Option<Verification>| {
+                    push_recovery_record(&recovery_results, "manual", &action.name, result.clone(), verification);
+                };
 
-                        // Wait if specified
-                        if let Some(wait_ms) = action.wait_after_ms {
-                            std::thread::sleep(Duration::from_millis(wait_ms));
-                        }
-                    }
-                    Err(e) => {
-                        let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
-                        if let Ok(mut log_vec) = logs.lock() {
-                            log_vec.push((
-                                format!("[{}] Recovery action '{}' failed: {}", timestamp, action.name, e),
-                                Color32::RED
-                            ));
-                        }
-                        return Err(format!("Recovery action '{}' failed: {}", action.name, e));
+                let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+                if !result.success {
+                    if let Ok(mut log_vec) = logs.lock() {
+                        log_vec.push((
+                            format!("[{}] Recovery action '{}' failed: {}", timestamp, action.name, result),
+                            Color32::RED
+                        ));
                     }
+                    record(None);
+                    return Err(format!("Recovery action '{}' failed: {}", action.name, result));
+                }
+                if let Ok(mut log_vec) = logs.lock() {
+                    log_vec.push((
+                        format!("[{}] Recovery action '{}' succeeded: {}", timestamp, action.name, result),
+                        Color32::GREEN
+                    ));
+                }
+
+                // Wait if specified
+                if let Some(wait_ms) = action.wait_after_ms {
+                    std::thread::sleep(Duration::from_millis(wait_ms));
+                }
+
+                // Stop at the first action whose verification passes
+                let verification = runtime.block_on(monitor::verify_recovery(config_ref, action));
+                record(Some(verification.clone()));
+                let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+                if let Ok(mut log_vec) = logs.lock() {
+                    let (status, color) = if verification.success {
+                        ("verified", Color32::GREEN)
+                    } else {
+                        ("not verified", Color32::YELLOW)
+                    };
+                    log_vec.push((
+                        format!("[{}] Recovery action '{}' {}: {}", timestamp, action.name, status, verification),
+                        color
+                    ));
+                }
+                if verification.success {
+                    return Ok(());
                 }
             }
 
@@ -284,17 +455,22 @@ impl NetworkMonitorApp {
         let parse_result = toml::from_str::<Config>(&self.config_editor_text);
         
         match parse_result {
-            Ok(new_config) => {
+            Ok(mut new_config) => {
                 // 설정 파일 저장 시도
                 let save_result = crate::config::save_config(&new_config, &self.config_path);
                 
                 match save_result {
                     Ok(_) => {
-                        // 설정 업데이트
-                        {
-                            if let Ok(mut config) = self.config.lock() {
-                                *config = new_config.clone(); // 복사본 사용
-                            }
+                        // 저장한 설정에는 자동 검색 대상이 없으므로 다시 채움
+                        monitor::refresh_discovered_targets(&mut new_config);
+                        let targets = new_config.targets.clone();
+                        
+                        // 실행 중인 모니터링 루프에도 새 설정 전달
+                        if let Some(config_updates) = &self.config_updates {
+                            config_updates.send_replace(new_config.clone());
+                        }
+                        if let Ok(mut config) = self.config.lock() {
+                            *config = new_config;
                         }
                         
                         self.show_config_editor = false;
@@ -302,24 +478,7 @@ impl NetworkMonitorApp {
                         self.add_log("Settings saved successfully", Color32::GREEN);
                         
                         // 대상 상태 업데이트
-                        // 설정의 복사본을 사용하여 불변 참조 문제 해결
-                        let targets = new_config.targets.clone();
-                        
-                        {
-                            if let Ok(mut statuses) = self.target_statuses.lock() {
-                                // 존재하지 않는 대상 제거
-                                statuses.retain(|name, _| {
-                                    targets.iter().any(|t| t.name == *name)
-                                });
-                                
-                                // 새 대상 추가
-                                for target in &targets {
-                                    if !statuses.contains_key(&target.name) {
-                                        statuses.insert(target.name.clone(), TargetStatus::new(target));
-                                    }
-                                }
-                            }
-                        }
+                        self.sync_target_statuses(&targets);
                     }
                     Err(e) => {
                         self.config_save_error = Some(format!("Failed to save settings: {}", e));
@@ -339,7 +498,7 @@ impl NetworkMonitorApp {
         // 설정을 직렬화하기 전에 먼저 config의 복사본을 만듭니다
         let config_clone = {
             if let Ok(config) = self.config.lock() {
-                Some(config.clone())
+                Some(config.without_discovered_targets())
             } else {
                 None
             }
@@ -365,6 +524,12 @@ impl NetworkMonitorApp {
 
 impl eframe::App for NetworkMonitorApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Show check results and state transitions from the monitoring loop
+        self.drain_monitor_events();
+        if self.monitoring_active {
+            ctx.request_repaint_after(Duration::from_secs(1));
+        }
+
         // Check recovery status
         if let Some(promise) = &self.recovery_promise {
             if let Some(result) = promise.ready() {
@@ -424,6 +589,7 @@ impl eframe::App for NetworkMonitorApp {
         egui::TopBottomPanel::top("tab_bar").show(ctx, |ui| {
             ui.horizontal(|ui| {
                 ui.selectable_value(&mut self.selected_tab, Tab::Status, "Status");
+                ui.selectable_value(&mut self.selected_tab, Tab::Interfaces, "Interfaces");
                 ui.selectable_value(&mut self.selected_tab, Tab::Settings, "Settings");
                 ui.selectable_value(&mut self.selected_tab, Tab::Logs, "Logs");
             });
@@ -433,6 +599,7 @@ impl eframe::App for NetworkMonitorApp {
         egui::CentralPanel::default().show(ctx, |ui| {
             match self.selected_tab {
                 Tab::Status => self.render_status_tab(ui),
+                Tab::Interfaces => self.render_interfaces_tab(ui),
                 Tab::Settings => self.render_settings_tab(ui),
                 Tab::Logs => self.render_logs_tab(ui),
             }
@@ -491,6 +658,9 @@ impl NetworkMonitorApp {
                 if ui.button("Run Recovery Actions").clicked() && !self.recovery_in_progress {
                     self.perform_recovery();
                 }
+                if crate::utils::is_dry_run_mode() {
+                    ui.colored_label(Color32::LIGHT_BLUE, "Dry run: recovery commands are logged, not executed");
+                }
                 
                 if self.recovery_in_progress {
                     ui.spinner();
@@ -502,47 +672,235 @@ impl NetworkMonitorApp {
             
             // Status grid
             egui::Grid::new("status_grid")
-                .num_columns(4)
+                .num_columns(10)
                 .striped(true)
                 .spacing([10.0, 5.0])
                 .show(ui, |ui| {
                     ui.strong("Target");
                     ui.strong("Address");
                     ui.strong("Status");
-                    ui.strong("Response Time");
+                    ui.strong("Health");
+                    ui.strong("Avg RTT");
+                    ui.strong("Loss");
+                    ui.strong("Min / Max");
+                    ui.strong("Jitter");
+                    ui.strong("Timing");
+                    ui.strong("Details");
                     ui.end_row();
                     
                     if let Ok(statuses) = self.target_statuses.lock() {
                         for (_, status) in statuses.iter() {
-                            ui.label(&status.name);
+                            if status.auto_discovered {
+                                ui.label(format!("{} [{}, auto]", status.name, status.kind));
+                            } else {
+                                ui.label(format!("{} [{}]", status.name, status.kind));
+                            }
                             
+                            let pings = status.pings();
+                            let host_text = match pings {
+                                [ping] if ping.address.to_string() != status.address => {
+                                    format!("{} ({})", status.address, ping.address)
+                                }
+                                _ => status.address.clone(),
+                            };
                             let address_text = if let Some(port) = status.port {
-                                format!("{}:{}", status.address, port)
+                                format!("{}:{}", host_text, port)
                             } else {
-                                status.address.clone()
+                                host_text
                             };
                             ui.label(address_text);
                             
                             // Status indicator
-                            if status.is_ok() {
+                            if status.probe_result.as_ref().is_some_and(|r| r.status == ProbeStatus::Warning) {
+                                ui.colored_label(Color32::YELLOW, "Warning");
+                            } else if status.is_ok() {
                                 ui.colored_label(Color32::GREEN, "Online");
-                            } else if status.ping_result.is_some() {
+                            } else if status.probe_result.is_some() {
                                 ui.colored_label(Color32::RED, "Offline");
                             } else {
                                 ui.colored_label(Color32::GRAY, "Unknown");
                             }
-                            
-                            // Response time
-                            if let Some(Ok(duration)) = &status.ping_result {
-                                ui.label(format!("{:.2} ms", duration.as_millis()));
+                            let maintenance_note = status
+                                .maintenance
+                                .as_ref()
+                                .map_or(String::new(), |window| format!(" [maintenance: {}]", window));
+                            if let Some(root_cause) = &status.root_cause {
+                                ui.colored_label(
+                                    health_color(status.health),
+                                    format!("{} (via {}){}", status.health, root_cause, maintenance_note),
+                                );
+                            } else if status.flapping {
+                                ui.colored_label(FLAPPING_COLOR, format!("{} (flapping){}", status.health, maintenance_note));
                             } else {
-                                ui.label("-");
+                                ui.colored_label(health_color(status.health), format!("{}{}", status.health, maintenance_note));
+                            }
+                            
+                            // Ping statistics / latency
+                            match &status.probe_result {
+                                Some(report) if !pings.is_empty() => {
+                                    render_ping_cells(ui, Some(primary_stats(pings).ok_or(())));
+                                    render_timing(ui, status.timing());
+                                    render_details(ui, report, !report.is_success());
+                                }
+                                Some(report) => {
+                                    match report.latency {
+                                        Some(latency) => ui.label(format!("{:.2} ms", as_millis_f64(latency))),
+                                        None => ui.label("-"),
+                                    };
+                                    for _ in 0..3 {
+                                        ui.label("-");
+                                    }
+                                    render_timing(ui, status.timing());
+                                    render_details(ui, report, true);
+                                }
+                                None => {
+                                    render_ping_cells(ui, None);
+                                    ui.label("-");
+                                    ui.label("-");
+                                }
+                            }
+                            
+                            // Dual-stack targets get one row per address family
+                            if pings.len() > 1 {
+                                for ping in pings {
+                                    ui.end_row();
+                                    ui.label(format!("  ↳ {}", ping.family_label()));
+                                    ui.label(ping.address.to_string());
+                                    if ping.result.is_ok() {
+                                        ui.colored_label(Color32::GREEN, "Online");
+                                    } else {
+                                        ui.colored_label(Color32::RED, "Offline");
+                                    }
+                                    ui.label("");
+                                    render_ping_cells(ui, Some(ping.result.as_ref().map_err(|_| ())));
+                                    ui.label("");
+                                    match &ping.result {
+                                        Ok(_) => ui.label(""),
+                                        Err(e) => ui.colored_label(Color32::RED, e),
+                                    };
+                                }
                             }
                             
                             ui.end_row();
                         }
                     }
                 });
+
+            // Recovery command results (exit code, duration, captured output)
+            let records = self.recovery_results.lock().map(|r| r.clone()).unwrap_or_default();
+            if !records.is_empty() {
+                ui.separator();
+                ui.collapsing("Recovery Results", |ui| {
+                    egui::Grid::new("recovery_results_grid")
+                        .num_columns(8)
+                        .striped(true)
+                        .spacing([10.0, 5.0])
+                        .show(ui, |ui| {
+                            ui.strong("Time");
+                            ui.strong("Group");
+                            ui.strong("Action");
+                            ui.strong("Command");
+                            ui.strong("Result");
+                            ui.strong("Duration");
+                            ui.strong("Output");
+                            ui.strong("Verification");
+                            ui.end_row();
+
+                            for record in records.iter().rev() {
+                                for (index, step) in record.result.steps.iter().enumerate() {
+                                    ui.label(&record.time);
+                                    ui.label(&record.group);
+                                    ui.label(&record.action);
+                                    ui.label(&step.command);
+                                    render_command_result(ui, step);
+                                    ui.label(format!("{:.1} s", step.duration.as_secs_f64()));
+                                    render_command_output(ui, step);
+                                    // The check runs once after the last command
+                                    match (&record.verification, index + 1 == record.result.steps.len()) {
+                                        (Some(verification), true) if verification.success => {
+                                            ui.colored_label(Color32::GREEN, verification.to_string());
+                                        }
+                                        (Some(verification), true) => {
+                                            ui.colored_label(Color32::YELLOW, verification.to_string());
+                                        }
+                                        _ => {
+                                            ui.label("-");
+                                        }
+                                    }
+                                    ui.end_row();
+                                }
+                            }
+                        });
+                });
+            }
+        });
+    }
+    
+    // Interfaces tab rendering
+    fn render_interfaces_tab(&mut self, ui: &mut Ui) {
+        ui.vertical(|ui| {
+            ui.heading("Network Interfaces");
+            
+            if ui.button("Refresh").clicked() || self.interfaces.is_none() {
+                self.interfaces = Some(network::get_network_interfaces().map_err(|e| e.to_string()));
+            }
+            
+            ui.separator();
+            
+            let interfaces = match &self.interfaces {
+                Some(Ok(interfaces)) => interfaces,
+                Some(Err(e)) => {
+                    ui.colored_label(Color32::RED, format!("Failed to list interfaces: {}", e));
+                    return;
+                }
+                None => return,
+            };
+            
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                egui::Grid::new("interfaces_grid")
+                    .num_columns(8)
+                    .striped(true)
+                    .spacing([10.0, 5.0])
+                    .show(ui, |ui| {
+                        ui.strong("Index");
+                        ui.strong("Name");
+                        ui.strong("State");
+                        ui.strong("MAC");
+                        ui.strong("MTU");
+                        ui.strong("Speed");
+                        ui.strong("Addresses");
+                        ui.strong("Gateway");
+                        ui.end_row();
+                        
+                        for interface in interfaces {
+                            ui.label(interface.index.to_string());
+                            if interface.has_default_route() {
+                                ui.strong(&interface.name);
+                            } else {
+                                ui.label(&interface.name);
+                            }
+                            let state_color = if interface.is_up() {
+                                Color32::GREEN
+                            } else {
+                                Color32::GRAY
+                            };
+                            ui.colored_label(state_color, interface.oper_state.to_string());
+                            ui.label(interface.mac.as_deref().unwrap_or("-"));
+                            ui.label(interface.mtu.map_or("-".to_string(), |mtu| mtu.to_string()));
+                            ui.label(interface.speed_mbps.map_or("-".to_string(), |speed| format!("{} Mbps", speed)));
+                            let addresses: Vec<String> = interface
+                                .ipv4
+                                .iter()
+                                .chain(&interface.ipv6)
+                                .map(|address| address.to_string())
+                                .collect();
+                            ui.label(if addresses.is_empty() { "-".to_string() } else { addresses.join("\n") });
+                            let gateways: Vec<String> = interface.gateways.iter().map(|g| g.to_string()).collect();
+                            ui.label(if gateways.is_empty() { "-".to_string() } else { gateways.join("\n") });
+                            ui.end_row();
+                        }
+                    });
+            });
         });
     }
     
@@ -594,19 +952,25 @@ impl NetworkMonitorApp {
                 ui.heading("Network Targets");
                 
                 egui::Grid::new("targets_grid")
-                    .num_columns(3)
+                    .num_columns(4)
                     .striped(true)
                     .spacing([10.0, 5.0])
                     .show(ui, |ui| {
                         ui.strong("Name");
                         ui.strong("Address");
                         ui.strong("Port");
+                        ui.strong("Probe");
                         ui.end_row();
                         
                         for target in &config.targets {
                             ui.label(&target.name);
                             ui.label(&target.address);
                             ui.label(target.port.map_or("None".to_string(), |p| p.to_string()));
+                            if target.auto_discovered {
+                                ui.label(format!("{} (auto)", probe::probe_kind(target)));
+                            } else {
+                                ui.label(probe::probe_kind(target));
+                            }
                             ui.end_row();
                         }
                     });
@@ -615,22 +979,63 @@ impl NetworkMonitorApp {
                 ui.heading("Recovery Actions");
                 
                 egui::Grid::new("recovery_grid")
-                    .num_columns(3)
+                    .num_columns(5)
                     .striped(true)
                     .spacing([10.0, 5.0])
                     .show(ui, |ui| {
+                        ui.strong("Group");
+                        ui.strong("Policy");
                         ui.strong("Name");
-                        ui.strong("Command");
+                        ui.strong("Action");
                         ui.strong("Wait Time");
                         ui.end_row();
                         
-                        for action in &config.recovery_actions {
-                            ui.label(&action.name);
-                            ui.label(&action.command);
-                            ui.label(action.wait_after_ms.map_or("None".to_string(), |w| format!("{} ms", w)));
-                            ui.end_row();
+                        for group in config.get_recovery_groups() {
+                            for action in &group.actions {
+                                ui.label(&group.name);
+                                ui.label(policy_label(&group));
+                                ui.label(&action.name);
+                                if action.dry_run.unwrap_or(false) {
+                                    ui.label(format!("{} (dry run)", action.kind));
+                                } else {
+                                    ui.label(action.kind.to_string());
+                                }
+                                ui.label(action.wait_after_ms.map_or("None".to_string(), |w| format!("{} ms", w)));
+                                ui.end_row();
+                            }
                         }
                     });
+
+                if !config.maintenance_windows.is_empty() {
+                    ui.separator();
+                    ui.heading("Maintenance Windows");
+
+                    let now = chrono::Local::now();
+                    egui::Grid::new("maintenance_grid")
+                        .num_columns(4)
+                        .striped(true)
+                        .spacing([10.0, 5.0])
+                        .show(ui, |ui| {
+                            ui.strong("Name");
+                            ui.strong("Schedule");
+                            ui.strong("Targets");
+                            ui.strong("Active");
+                            ui.end_row();
+
+                            for window in &config.maintenance_windows {
+                                ui.label(&window.name);
+                                ui.label(schedule_label(window));
+                                let selection: Vec<&str> = window.targets.iter().chain(&window.tags).map(String::as_str).collect();
+                                ui.label(if selection.is_empty() { "all".to_string() } else { selection.join(", ") });
+                                if window.is_active(&now) {
+                                    ui.colored_label(Color32::LIGHT_BLUE, "Yes");
+                                } else {
+                                    ui.label("No");
+                                }
+                                ui.end_row();
+                            }
+                        });
+                }
             }
         });
     }
@@ -657,6 +1062,140 @@ impl NetworkMonitorApp {
     }
 }
 
+// Render the Avg RTT / Loss / Min-Max / Jitter cells of a status row
+fn render_ping_cells(ui: &mut Ui, ping_result: Option<Result<&PingStats, ()>>) {
+    match ping_result {
+        Some(Ok(stats)) => {
+            ui.label(format!("{:.2} ms", as_millis_f64(stats.avg)));
+            let loss_color = if stats.loss_percent > 0.0 {
+                Color32::YELLOW
+            } else {
+                Color32::GREEN
+            };
+            ui.colored_label(
+                loss_color,
+                format!("{:.1}% ({}/{})", stats.loss_percent, stats.received, stats.transmitted),
+            );
+            ui.label(format!("{:.2} / {:.2} ms", as_millis_f64(stats.min), as_millis_f64(stats.max)));
+            ui.label(format!("{:.2} ms", as_millis_f64(stats.jitter)));
+        }
+        Some(Err(_)) => {
+            ui.label("-");
+            ui.colored_label(Color32::RED, "100%");
+            ui.label("-");
+            ui.label("-");
+        }
+        None => {
+            for _ in 0..4 {
+                ui.label("-");
+            }
+        }
+    }
+}
+
+// Outage policy and target selection of a recovery group, e.g. "2 of [internet]"
+fn policy_label(group: &RecoveryGroup) -> String {
+    let policy = match &group.policy {
+        OutagePolicy::All => "all down".to_string(),
+        OutagePolicy::Quorum { min_down } => format!("{}+ down", min_down),
+        OutagePolicy::Any => "any down".to_string(),
+        OutagePolicy::Weighted { min_score } => format!("weight >= {}", min_score),
+    };
+    let selection: Vec<&str> = group.targets.iter().chain(&group.tags).map(String::as_str).collect();
+    if selection.is_empty() {
+        policy
+    } else {
+        format!("{} of [{}]", policy, selection.join(", "))
+    }
+}
+
+// Schedule of a maintenance window, e.g. "0 3 * * 0 (60 min)" or "mon, sat 02:00-04:00"
+fn schedule_label(window: &MaintenanceWindow) -> String {
+    if let Some(cron) = &window.cron {
+        return format!("{} ({} min)", cron, window.duration_min.unwrap_or(0));
+    }
+    let days = if window.days.is_empty() {
+        "daily".to_string()
+    } else {
+        window.days.join(", ")
+    };
+    match (&window.start, &window.end) {
+        (Some(start), Some(end)) => format!("{} {}-{}", days, start, end),
+        _ => format!("{} (all day)", days),
+    }
+}
+
+// Color for flapping targets and flapping-started log lines
+const FLAPPING_COLOR: Color32 = Color32::GOLD;
+
+// Exit status cell of a recovery command
+fn render_command_result(ui: &mut Ui, result: &network::recovery::CommandResult) {
+    let color = if result.success { Color32::GREEN } else { Color32::RED };
+    let text = match (&result.error, result.timed_out, result.exit_code) {
+        (Some(_), _, _) => "Failed to start".to_string(),
+        (None, true, _) => "Timed out".to_string(),
+        (None, false, Some(code)) => format!("Exit {}", code),
+        (None, false, None) => "Killed".to_string(),
+    };
+    let label = ui.colored_label(color, text);
+    if let Some(error) = &result.error {
+        label.on_hover_text(error);
+    }
+}
+
+// First line of captured output; the full (capped) stdout/stderr is shown on hover
+fn render_command_output(ui: &mut Ui, result: &network::recovery::CommandResult) {
+    let output = if result.stderr.trim().is_empty() || result.success {
+        result.stdout.trim()
+    } else {
+        result.stderr.trim()
+    };
+    let mut summary = output.lines().next().unwrap_or("").to_string();
+    if result.truncated {
+        summary.push_str(" (truncated)");
+    }
+    let label = ui.label(summary);
+    if !result.stdout.trim().is_empty() || !result.stderr.trim().is_empty() {
+        label.on_hover_text(format!("stdout:\n{}\n\nstderr:\n{}", result.stdout.trim(), result.stderr.trim()));
+    }
+}
+
+// Color for a health state label or transition log line
+fn health_color(state: HealthState) -> Color32 {
+    match state {
+        HealthState::Up => Color32::GREEN,
+        HealthState::Degraded | HealthState::Recovering => Color32::YELLOW,
+        HealthState::Down => Color32::RED,
+        HealthState::Unreachable => Color32::LIGHT_RED,
+        HealthState::Unknown => Color32::GRAY,
+    }
+}
+
+// Render the DNS / TCP / TLS / first-byte breakdown in the Timing column
+fn render_timing(ui: &mut Ui, timing: Option<&ConnectionTiming>) {
+    match timing {
+        Some(timing) => ui.label(timing.to_string()).on_hover_text(format!(
+            "{} (total {:.2} ms)",
+            timing.peer,
+            as_millis_f64(timing.total())
+        )),
+        None => ui.label("-"),
+    };
+}
+
+// Render the probe message in the Details column
+fn render_details(ui: &mut Ui, report: &ProbeReport, show_message: bool) {
+    if !show_message {
+        ui.label("");
+        return;
+    }
+    match report.status {
+        ProbeStatus::Ok => ui.label(&report.message),
+        ProbeStatus::Warning => ui.colored_label(Color32::YELLOW, &report.message),
+        ProbeStatus::Failed => ui.colored_label(Color32::RED, &report.message),
+    };
+}
+
 // Run GUI application
 pub fn run_gui(config_path: String) -> Result<(), eframe::Error> {
     let options = eframe::NativeOptions {
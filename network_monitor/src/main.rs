@@ -1,6 +1,8 @@
 mod config;
 mod monitor;
 mod network;
+mod probe;
+#[cfg(windows)]
 mod service;
 mod utils;
 
@@ -24,11 +26,16 @@ struct Cli {
     /// 디버그 모드 활성화
     #[arg(short, long)]
     debug: bool,
+
+    /// 복구 작업을 실행하지 않고 실행할 명령만 기록 (모의 실행)
+    #[arg(long, global = true)]
+    dry_run: bool,
 }
 
 #[derive(Subcommand, Debug)]
 enum Commands {
     /// 서비스로 실행
+    #[cfg(windows)]
     Service {
         /// 서비스 설치
         #[arg(short, long)]
@@ -43,7 +50,7 @@ enum Commands {
     /// 네트워크 연결 테스트
     Test {
         /// 테스트할 호스트 주소
-        #[arg(short, long)]
+        #[arg(short = 'H', long)]
         host: Option<String>,
     },
     /// GUI 모드로 실행
@@ -68,9 +75,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     if cli.debug {
         utils::set_debug_mode(true);
     }
+    if cli.dry_run {
+        utils::set_dry_run_mode(true);
+    }
     
     // 명령줄에서 직접 실행할 때만 콘솔 로거 초기화 (서비스 모드가 아닐 때)
-    if !matches!(cli.command, Some(Commands::Service { .. })) {
+    #[cfg(windows)]
+    let service_mode = matches!(cli.command, Some(Commands::Service { .. }));
+    #[cfg(not(windows))]
+    let service_mode = false;
+    if !service_mode {
         match utils::logging::setup_console_logger() {
             Ok(_) => {},
             Err(e) => {
@@ -97,6 +111,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     
     // 명령 처리
     match &cli.command {
+        #[cfg(windows)]
         Some(Commands::Service { install, uninstall }) => {
             if *install {
                 info!("서비스 설치 중...");
@@ -144,7 +159,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                 Ok(_) => {},
                 Err(e) => {
                     error!("네트워크 연결 테스트 실패: {}", e);
-                    return Err(Box::new(std::io::Error::new(std::io::ErrorKind::Other, format!("네트워크 연결 테스트 실패: {}", e))) as Box<dyn std::error::Error + Send + Sync>);
+                    return Err(Box::new(std::io::Error::other(format!("네트워크 연결 테스트 실패: {}", e))) as Box<dyn std::error::Error + Send + Sync>);
                 }
             }
         },
@@ -155,7 +170,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                 Ok(_) => {},
                 Err(e) => {
                     error!("GUI 실행 실패: {}", e);
-                    return Err(Box::new(std::io::Error::new(std::io::ErrorKind::Other, format!("GUI 실행 실패: {}", e))) as Box<dyn std::error::Error + Send + Sync>);
+                    return Err(Box::new(std::io::Error::other(format!("GUI 실행 실패: {}", e))) as Box<dyn std::error::Error + Send + Sync>);
                 }
             }
         },
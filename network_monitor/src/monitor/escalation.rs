@@ -0,0 +1,352 @@
+use crate::config::{RecoveryAction, RecoveryGroup};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+/// 실행 횟수 예산을 세는 구간
+const ATTEMPT_BUDGET_WINDOW: Duration = Duration::from_secs(3600);
+
+/// 복구 그룹의 다음 단계입니다.
+#[derive(Debug, Clone, Copy)]
+pub enum EscalationStep<'a> {
+    /// 이 복구 작업을 실행 (`rung`은 단계 번호, 1부터 시작)
+    Run { action: &'a RecoveryAction, rung: usize },
+    /// 실행할 단계가 없음 (min_failure_sec 대기 중이거나 마지막 단계의 cooldown 중)
+    Wait,
+    /// 모든 단계가 실행 횟수 예산을 소진함. 장애가 해소될 때까지 더 실행하지 않습니다.
+    GiveUp,
+}
+
+/// 한 번의 장애 (장애 판단부터 해소까지)
+#[derive(Debug)]
+struct Episode {
+    since: Instant,
+    /// 복구 작업별 이번 장애 중 실행 횟수와 마지막 실행 시각
+    runs: HashMap<String, (u32, Instant)>,
+    /// 이번 장애 중 실행에 실패했거나 확인에 실패한 복구 작업
+    failed: HashSet<String>,
+    gave_up: bool,
+    /// 확인에 성공한 마지막 복구 작업
+    fixed_by: Option<String>,
+}
+
+/// 해소된 장애의 요약입니다.
+#[derive(Debug, Clone)]
+pub struct Resolution {
+    pub duration: Duration,
+    /// 장애 중 확인에 성공한 마지막 복구 작업 (없으면 복구 작업 없이 해소됨)
+    pub fixed_by: Option<String>,
+}
+
+/// (그룹, 복구 작업)별 누적 실행 결과입니다.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ActionOutcomes {
+    /// 실행 후 확인한 횟수
+    pub verified_runs: u32,
+    /// 확인에 성공한 횟수
+    pub fixes: u32,
+}
+
+/// 복구 그룹별 장애와 복구 작업 실행 이력을 추적해 단계적으로 복구 작업을 고릅니다.
+///
+/// 이번 장애 중 아직 실행하지 않은 가장 가벼운(severity가 작은) 단계부터 실행합니다.
+/// 실행한 단계가 실패(실행 또는 확인 실패)하거나 cooldown이 지나도록 장애가 이어지면 다음 단계로 올라가며,
+/// 다음 단계는 장애 시간이 `min_failure_sec`에 도달해야 실행합니다. 모든 단계를 실행한 뒤에는
+/// cooldown이 지난 가장 무거운 단계를 반복합니다. 최근 1시간 실행 횟수가 예산을 넘은 단계는 건너뛰며,
+/// 모든 단계가 예산을 소진하면 포기합니다.
+#[derive(Debug, Default)]
+pub struct EscalationTracker {
+    episodes: HashMap<String, Episode>,
+    /// (그룹, 복구 작업)별 최근 1시간 실행 시각 (장애가 해소되어도 유지)
+    attempts: HashMap<(String, String), VecDeque<Instant>>,
+    /// (그룹, 복구 작업)별 확인 결과 (장애가 해소되어도 유지)
+    outcomes: HashMap<(String, String), ActionOutcomes>,
+}
+
+impl EscalationTracker {
+    /// 그룹의 장애를 기록합니다. 이미 장애 중이면 시작 시각을 유지합니다.
+    pub fn begin(&mut self, group: &str, now: Instant) {
+        self.episodes.entry(group.to_string()).or_insert_with(|| Episode {
+            since: now,
+            runs: HashMap::new(),
+            failed: HashSet::new(),
+            gave_up: false,
+            fixed_by: None,
+        });
+    }
+
+    /// 그룹의 장애가 해소되었음을 기록합니다. 장애 중이었으면 장애 시간과 복구한 작업을 반환합니다.
+    pub fn resolve(&mut self, group: &str, now: Instant) -> Option<Resolution> {
+        self.episodes.remove(group).map(|episode| Resolution {
+            duration: now.duration_since(episode.since),
+            fixed_by: episode.fixed_by,
+        })
+    }
+
+    /// 장애 중인 그룹에서 다음에 실행할 단계를 고릅니다.
+    pub fn next_step<'a>(&mut self, group: &'a RecoveryGroup, now: Instant) -> EscalationStep<'a> {
+        let Some(episode) = self.episodes.get_mut(&group.name) else {
+            return EscalationStep::Wait;
+        };
+        if episode.gave_up {
+            return EscalationStep::Wait;
+        }
+
+        let elapsed = now.duration_since(episode.since);
+        let ladder: Vec<(usize, &RecoveryAction)> = group
+            .ladder()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, action)| {
+                let history = self.attempts.entry((group.name.clone(), action.name.clone())).or_default();
+                while history.front().is_some_and(|at| now.duration_since(*at) >= ATTEMPT_BUDGET_WINDOW) {
+                    history.pop_front();
+                }
+                history.len() < action.get_max_attempts_per_hour() as usize
+            })
+            .collect();
+        if ladder.is_empty() {
+            episode.gave_up = true;
+            return EscalationStep::GiveUp;
+        }
+
+        let cooling_down = |action: &RecoveryAction| {
+            episode
+                .runs
+                .get(&action.name)
+                .is_some_and(|(runs, last_run)| now.duration_since(*last_run) < action.get_cooldown(*runs))
+        };
+
+        // 가벼운 단계부터: 실행하지 않은 단계가 나오면 실행하고,
+        // 실행한 단계가 아직 실패하지 않았고 cooldown 중이면 그 결과를 기다림
+        for &(index, action) in &ladder {
+            if !episode.runs.contains_key(&action.name) {
+                if elapsed < action.get_min_failure_duration() {
+                    return EscalationStep::Wait;
+                }
+                return EscalationStep::Run { action, rung: index + 1 };
+            }
+            if !episode.failed.contains(&action.name) && cooling_down(action) {
+                return EscalationStep::Wait;
+            }
+        }
+
+        // 모든 단계를 실행했으면 cooldown이 지난 가장 무거운 단계를 반복
+        ladder
+            .iter()
+            .rev()
+            .find(|(_, action)| !cooling_down(action))
+            .map_or(EscalationStep::Wait, |&(index, action)| EscalationStep::Run { action, rung: index + 1 })
+    }
+
+    /// 복구 작업을 실행했음을 기록합니다.
+    pub fn record_run(&mut self, group: &str, action: &RecoveryAction, now: Instant) {
+        self.attempts
+            .entry((group.to_string(), action.name.clone()))
+            .or_default()
+            .push_back(now);
+        if let Some(episode) = self.episodes.get_mut(group) {
+            let runs = episode.runs.entry(action.name.clone()).or_insert((0, now));
+            runs.0 += 1;
+            runs.1 = now;
+        }
+    }
+
+    /// 복구 작업 실행에 실패했음을 기록합니다. 다음 단계로 바로 올라갈 수 있습니다.
+    pub fn record_failure(&mut self, group: &str, action: &RecoveryAction) {
+        if let Some(episode) = self.episodes.get_mut(group) {
+            episode.failed.insert(action.name.clone());
+        }
+    }
+
+    /// 복구 작업 후 확인 결과를 기록하고 누적 결과를 반환합니다.
+    /// 확인에 실패하면 다음 단계로 바로 올라갈 수 있습니다.
+    pub fn record_outcome(&mut self, group: &str, action: &RecoveryAction, verified: bool) -> ActionOutcomes {
+        if let Some(episode) = self.episodes.get_mut(group) {
+            if verified {
+                episode.fixed_by = Some(action.name.clone());
+            } else {
+                episode.failed.insert(action.name.clone());
+            }
+        }
+        let outcomes = self
+            .outcomes
+            .entry((group.to_string(), action.name.clone()))
+            .or_default();
+        outcomes.verified_runs += 1;
+        if verified {
+            outcomes.fixes += 1;
+        }
+        *outcomes
+    }
+
+    /// 현재 장애가 시작된 뒤 지난 시간
+    pub fn outage_duration(&self, group: &str, now: Instant) -> Option<Duration> {
+        self.episodes.get(group).map(|episode| now.duration_since(episode.since))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// light(가벼운 단계), heavy(무거운 단계) 두 단계로 된 그룹. 각 단계의 추가 설정을 받습니다.
+    fn group(light: &str, heavy: &str) -> RecoveryGroup {
+        toml::from_str(&format!(
+            "name = \"g\"\n\
+             [[actions]]\nname = \"heavy\"\ncommand = \"heavy\"\nseverity = 2\ncooldown_sec = 60\nbackoff_multiplier = 1.0\n{}\n\
+             [[actions]]\nname = \"light\"\ncommand = \"light\"\nseverity = 1\ncooldown_sec = 60\nbackoff_multiplier = 1.0\n{}\n",
+            heavy, light
+        ))
+        .unwrap()
+    }
+
+    fn secs(start: Instant, secs: u64) -> Instant {
+        start + Duration::from_secs(secs)
+    }
+
+    /// 다음 단계가 실행이면 실행을 기록하고 작업 이름을 반환합니다.
+    fn run(tracker: &mut EscalationTracker, group: &RecoveryGroup, now: Instant) -> Option<String> {
+        match tracker.next_step(group, now) {
+            EscalationStep::Run { action, .. } => {
+                tracker.record_run(&group.name, action, now);
+                Some(action.name.clone())
+            }
+            _ => None,
+        }
+    }
+
+    fn action<'a>(group: &'a RecoveryGroup, name: &str) -> &'a RecoveryAction {
+        group.actions.iter().find(|a| a.name == name).unwrap()
+    }
+
+    #[test]
+    fn waits_without_an_outage() {
+        let group = group("", "");
+        let mut tracker = EscalationTracker::default();
+        assert!(matches!(tracker.next_step(&group, Instant::now()), EscalationStep::Wait));
+    }
+
+    #[test]
+    fn starts_from_lightest_rung_without_thresholds() {
+        let group = group("", "");
+        let mut tracker = EscalationTracker::default();
+        let start = Instant::now();
+        tracker.begin("g", start);
+        match tracker.next_step(&group, start) {
+            EscalationStep::Run { action, rung } => {
+                assert_eq!(action.name, "light");
+                assert_eq!(rung, 1);
+            }
+            step => panic!("unexpected step {:?}", step),
+        }
+    }
+
+    #[test]
+    fn moves_up_after_failed_verification() {
+        let group = group("", "");
+        let mut tracker = EscalationTracker::default();
+        let start = Instant::now();
+        tracker.begin("g", start);
+        assert_eq!(run(&mut tracker, &group, start).as_deref(), Some("light"));
+        // 확인 결과를 기다리는 동안(cooldown 중)은 올라가지 않음
+        assert!(matches!(tracker.next_step(&group, secs(start, 10)), EscalationStep::Wait));
+
+        tracker.record_outcome("g", action(&group, "light"), false);
+        assert_eq!(run(&mut tracker, &group, secs(start, 10)).as_deref(), Some("heavy"));
+    }
+
+    #[test]
+    fn moves_up_after_command_failure() {
+        let group = group("", "");
+        let mut tracker = EscalationTracker::default();
+        let start = Instant::now();
+        tracker.begin("g", start);
+        run(&mut tracker, &group, start);
+        tracker.record_failure("g", action(&group, "light"));
+        assert_eq!(run(&mut tracker, &group, secs(start, 1)).as_deref(), Some("heavy"));
+    }
+
+    #[test]
+    fn moves_up_after_cooldown_when_outage_persists() {
+        let group = group("", "");
+        let mut tracker = EscalationTracker::default();
+        let start = Instant::now();
+        tracker.begin("g", start);
+        run(&mut tracker, &group, start);
+        assert!(matches!(tracker.next_step(&group, secs(start, 59)), EscalationStep::Wait));
+        assert_eq!(run(&mut tracker, &group, secs(start, 60)).as_deref(), Some("heavy"));
+    }
+
+    #[test]
+    fn heavier_rung_waits_for_min_failure() {
+        let group = group("", "min_failure_sec = 300");
+        let mut tracker = EscalationTracker::default();
+        let start = Instant::now();
+        tracker.begin("g", start);
+        run(&mut tracker, &group, start);
+        tracker.record_outcome("g", action(&group, "light"), false);
+        assert!(matches!(tracker.next_step(&group, secs(start, 100)), EscalationStep::Wait));
+        assert_eq!(run(&mut tracker, &group, secs(start, 300)).as_deref(), Some("heavy"));
+    }
+
+    #[test]
+    fn repeats_rungs_after_ladder_is_tried() {
+        let group = group("", "");
+        let mut tracker = EscalationTracker::default();
+        let start = Instant::now();
+        tracker.begin("g", start);
+        run(&mut tracker, &group, start);
+        tracker.record_outcome("g", action(&group, "light"), false);
+        run(&mut tracker, &group, secs(start, 30));
+        // 마지막 단계의 결과를 기다리는 동안은 가벼운 단계로 돌아가지 않음
+        assert!(matches!(tracker.next_step(&group, secs(start, 60)), EscalationStep::Wait));
+
+        // heavy가 실패했고 cooldown 중이면 cooldown이 지난 light를 다시 실행
+        tracker.record_outcome("g", action(&group, "heavy"), false);
+        assert_eq!(run(&mut tracker, &group, secs(start, 60)).as_deref(), Some("light"));
+        assert!(matches!(tracker.next_step(&group, secs(start, 70)), EscalationStep::Wait));
+        // 둘 다 cooldown이 지나면 무거운 단계부터 반복
+        assert_eq!(run(&mut tracker, &group, secs(start, 120)).as_deref(), Some("heavy"));
+    }
+
+    #[test]
+    fn gives_up_when_every_budget_is_spent() {
+        let group = group("max_attempts_per_hour = 1", "max_attempts_per_hour = 1");
+        let mut tracker = EscalationTracker::default();
+        let start = Instant::now();
+        tracker.begin("g", start);
+        assert_eq!(run(&mut tracker, &group, start).as_deref(), Some("light"));
+        assert_eq!(run(&mut tracker, &group, secs(start, 60)).as_deref(), Some("heavy"));
+        assert!(matches!(tracker.next_step(&group, secs(start, 120)), EscalationStep::GiveUp));
+        // 한 번 포기하면 장애가 해소될 때까지 다시 알리지 않음
+        assert!(matches!(tracker.next_step(&group, secs(start, 180)), EscalationStep::Wait));
+
+        // 장애가 해소된 뒤 새 장애는 예산이 남은 단계가 없으므로 바로 포기
+        assert!(tracker.resolve("g", secs(start, 200)).is_some());
+        tracker.begin("g", secs(start, 200));
+        assert!(matches!(tracker.next_step(&group, secs(start, 200)), EscalationStep::GiveUp));
+    }
+
+    #[test]
+    fn skips_rungs_without_budget() {
+        let group = group("max_attempts_per_hour = 0", "");
+        let mut tracker = EscalationTracker::default();
+        let start = Instant::now();
+        tracker.begin("g", start);
+        assert_eq!(run(&mut tracker, &group, start).as_deref(), Some("heavy"));
+    }
+
+    #[test]
+    fn resolution_reports_fixing_action() {
+        let group = group("", "");
+        let mut tracker = EscalationTracker::default();
+        let start = Instant::now();
+        tracker.begin("g", start);
+        run(&mut tracker, &group, start);
+        let outcomes = tracker.record_outcome("g", action(&group, "light"), true);
+        assert_eq!((outcomes.verified_runs, outcomes.fixes), (1, 1));
+        let resolution = tracker.resolve("g", secs(start, 30)).unwrap();
+        assert_eq!(resolution.fixed_by.as_deref(), Some("light"));
+        assert_eq!(resolution.duration, Duration::from_secs(30));
+    }
+}
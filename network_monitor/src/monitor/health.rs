@@ -0,0 +1,529 @@
+use crate::config::{Config, NetworkTarget};
+use chrono::Local;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// 대상의 건강 상태입니다.
+///
+/// 연속 실패가 `fail_threshold`에 도달하면 Down, Down 이후 연속 성공이
+/// `recover_threshold`에 도달하면 Up이 됩니다. 그 사이는 Degraded/Recovering입니다.
+/// `depends_on`으로 지정한 상위 대상이 Down이면 확인하지 않고 Unreachable이 됩니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HealthState {
+    /// 아직 확인 결과가 없음
+    #[default]
+    Unknown,
+    Up,
+    /// 실패했지만 아직 fail_threshold에 도달하지 않음
+    Degraded,
+    Down,
+    /// Down 이후 성공했지만 아직 recover_threshold에 도달하지 않음
+    Recovering,
+    /// 상위 대상의 장애로 연결할 수 없음
+    Unreachable,
+}
+
+impl fmt::Display for HealthState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Self::Unknown => "unknown",
+            Self::Up => "up",
+            Self::Degraded => "degraded",
+            Self::Down => "down",
+            Self::Recovering => "recovering",
+            Self::Unreachable => "unreachable",
+        };
+        f.write_str(text)
+    }
+}
+
+/// 상태 이벤트 종류
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthEventKind {
+    /// 상태 전이. 플래핑 중에 일어난 전이는 `flapping`이 true입니다.
+    Transition {
+        from: HealthState,
+        to: HealthState,
+        /// 전이를 일으킨 연속 성공 또는 실패 횟수
+        consecutive: u32,
+        flapping: bool,
+    },
+    /// 상위 대상(`root_cause`)이 Down이어서 확인하지 않음
+    Unreachable { from: HealthState, root_cause: String },
+    /// 감시 구간 안의 상태 변경 횟수가 flap_threshold에 도달함
+    FlappingStarted { changes: usize, window: Duration },
+    /// 상태 변경 횟수가 flap_threshold의 절반 이하로 줄어듦
+    FlappingStopped { state: HealthState },
+}
+
+/// 대상의 상태 이벤트입니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthEvent {
+    pub target: String,
+    pub kind: HealthEventKind,
+    /// 이벤트가 일어난 점검 시간 이름 (점검 중에는 알림을 보내지 않음)
+    pub maintenance: Option<String>,
+}
+
+impl HealthEvent {
+    /// 알림을 보낼 이벤트인지 여부.
+    /// 장애 발생(Down 진입)과 장애 복구(Down/Recovering → Up), 플래핑 시작/종료가 해당하며
+    /// 플래핑 중의 개별 전이와 점검 시간 중의 이벤트는 제외합니다.
+    pub fn should_notify(&self) -> bool {
+        if self.maintenance.is_some() {
+            return false;
+        }
+        match &self.kind {
+            HealthEventKind::Transition { from, to, flapping, .. } => {
+                !flapping
+                    && (*to == HealthState::Down
+                        || (*to == HealthState::Up && matches!(from, HealthState::Down | HealthState::Recovering)))
+            }
+            HealthEventKind::FlappingStarted { .. } | HealthEventKind::FlappingStopped { .. } => true,
+            // 알림은 근본 원인인 상위 대상의 장애로만 보냄
+            HealthEventKind::Unreachable { .. } => false,
+        }
+    }
+
+    /// 이벤트 이후의 상태 (알림의 NETWORK_MONITOR_STATE 값)
+    pub fn state_label(&self) -> String {
+        match &self.kind {
+            HealthEventKind::Transition { to, .. } => to.to_string(),
+            HealthEventKind::Unreachable { .. } => HealthState::Unreachable.to_string(),
+            HealthEventKind::FlappingStarted { .. } => "flapping".to_string(),
+            HealthEventKind::FlappingStopped { state } => state.to_string(),
+        }
+    }
+
+    /// 이벤트 이전의 상태 (알림의 NETWORK_MONITOR_PREVIOUS_STATE 값)
+    pub fn previous_state_label(&self) -> String {
+        match &self.kind {
+            HealthEventKind::Transition { from, .. } | HealthEventKind::Unreachable { from, .. } => from.to_string(),
+            HealthEventKind::FlappingStarted { .. } => "stable".to_string(),
+            HealthEventKind::FlappingStopped { .. } => "flapping".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for HealthEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            HealthEventKind::Transition { from, to, consecutive, flapping } => {
+                let result = match to {
+                    HealthState::Up | HealthState::Recovering => "성공",
+                    _ => "실패",
+                };
+                write!(
+                    f,
+                    "대상 '{}' 상태 변경: {} → {} (연속 {} {}회)",
+                    self.target, from, to, result, consecutive
+                )?;
+                if *flapping {
+                    write!(f, " [플래핑 중, 알림 생략]")?;
+                }
+            }
+            HealthEventKind::Unreachable { from, root_cause } => write!(
+                f,
+                "대상 '{}' 상태 변경: {} → unreachable (상위 대상 '{}' 장애)",
+                self.target, from, root_cause
+            )?,
+            HealthEventKind::FlappingStarted { changes, window } => write!(
+                f,
+                "대상 '{}' 플래핑 시작: 최근 {}초 동안 상태 변경 {}회",
+                self.target,
+                window.as_secs(),
+                changes
+            )?,
+            HealthEventKind::FlappingStopped { state } => {
+                write!(f, "대상 '{}' 플래핑 종료: 현재 상태 {}", self.target, state)?
+            }
+        }
+        if let Some(window) = &self.maintenance {
+            write!(f, " [점검 중: {}, 알림 생략]", window)?;
+        }
+        Ok(())
+    }
+}
+
+/// 대상 하나의 상태와 연속 결과 횟수
+#[derive(Debug, Clone, Default)]
+pub struct TargetHealth {
+    pub state: HealthState,
+    pub consecutive_failures: u32,
+    pub consecutive_successes: u32,
+    /// 감시 구간 안에서 일어난 상태 변경 시각
+    pub changes: VecDeque<Instant>,
+    pub flapping: bool,
+}
+
+impl TargetHealth {
+    /// 확인 결과를 반영하고, 상태가 바뀌었으면 새 상태를 반환합니다.
+    pub fn record(&mut self, success: bool, fail_threshold: u32, recover_threshold: u32) -> Option<HealthState> {
+        if success {
+            self.consecutive_successes += 1;
+            self.consecutive_failures = 0;
+        } else {
+            self.consecutive_failures += 1;
+            self.consecutive_successes = 0;
+        }
+
+        let next = match (self.state, success) {
+            (HealthState::Unknown | HealthState::Degraded | HealthState::Unreachable, true) => HealthState::Up,
+            (HealthState::Down | HealthState::Recovering, true) => {
+                if self.consecutive_successes >= recover_threshold {
+                    HealthState::Up
+                } else {
+                    HealthState::Recovering
+                }
+            }
+            (HealthState::Up, true) => HealthState::Up,
+            (HealthState::Unknown | HealthState::Up | HealthState::Degraded | HealthState::Unreachable, false) => {
+                if self.consecutive_failures >= fail_threshold {
+                    HealthState::Down
+                } else {
+                    HealthState::Degraded
+                }
+            }
+            (HealthState::Down | HealthState::Recovering, false) => HealthState::Down,
+        };
+
+        if next == self.state {
+            return None;
+        }
+        self.state = next;
+        Some(next)
+    }
+
+    /// 상위 대상의 장애로 Unreachable로 표시합니다. 상위 대상이 복구된 뒤에는
+    /// 연속 결과 횟수를 처음부터 다시 셉니다. 상태가 바뀌었으면 이전 상태를 반환합니다.
+    pub fn mark_unreachable(&mut self) -> Option<HealthState> {
+        self.consecutive_failures = 0;
+        self.consecutive_successes = 0;
+        if self.state == HealthState::Unreachable {
+            return None;
+        }
+        Some(std::mem::replace(&mut self.state, HealthState::Unreachable))
+    }
+
+    /// 감시 구간 안의 상태 변경 횟수로 플래핑 여부를 갱신하고, 바뀌었으면 새 값을 반환합니다.
+    /// 변경 횟수가 `threshold` 이상이면 플래핑을 시작하고, 절반 이하로 줄면 종료합니다.
+    /// `threshold`가 0이면 플래핑을 감지하지 않습니다.
+    pub fn update_flapping(&mut self, now: Instant, window: Duration, threshold: u32) -> Option<bool> {
+        while self.changes.front().is_some_and(|at| now.duration_since(*at) > window) {
+            self.changes.pop_front();
+        }
+
+        let changes = self.changes.len();
+        let threshold = threshold as usize;
+        let flapping = if threshold == 0 {
+            false
+        } else if self.flapping {
+            changes > threshold / 2
+        } else {
+            changes >= threshold
+        };
+
+        if flapping == self.flapping {
+            return None;
+        }
+        self.flapping = flapping;
+        Some(flapping)
+    }
+}
+
+/// 모든 대상의 상태를 추적합니다.
+#[derive(Debug, Default)]
+pub struct HealthTracker {
+    targets: HashMap<String, TargetHealth>,
+}
+
+impl HealthTracker {
+    /// 대상의 확인 결과를 반영하고, 상태 전이와 플래핑 시작/종료 이벤트를 반환합니다.
+    /// 실패했을 때 상위 대상도 실패 중(Degraded/Down)이면 자체 장애로 세지 않고 Unreachable로 표시합니다.
+    pub fn record(&mut self, config: &Config, target: &NetworkTarget, success: bool) -> Vec<HealthEvent> {
+        if !success {
+            if let Some(parent) = self.find_ancestor(config, target, &[HealthState::Degraded, HealthState::Down]) {
+                return self.record_unreachable(config, target, &parent).into_iter().collect();
+            }
+        }
+
+        let now = Instant::now();
+        let health = self.targets.entry(target.name.clone()).or_default();
+        let from = health.state;
+        let transition = health.record(
+            success,
+            config.get_target_fail_threshold(target),
+            config.get_target_recover_threshold(target),
+        );
+        // 처음 결과를 받은 것은 상태 변경 횟수에 포함하지 않음
+        if transition.is_some() && from != HealthState::Unknown {
+            health.changes.push_back(now);
+        }
+        let flapping = health.update_flapping(
+            now,
+            config.get_target_flap_window(target),
+            config.get_target_flap_threshold(target),
+        );
+
+        let maintenance = maintenance_name(config, target);
+        let mut events = Vec::new();
+        if let Some(to) = transition {
+            let consecutive = if success { health.consecutive_successes } else { health.consecutive_failures };
+            events.push(HealthEvent {
+                target: target.name.clone(),
+                kind: HealthEventKind::Transition {
+                    from,
+                    to,
+                    consecutive,
+                    // 플래핑을 시작하게 만든 전이도 개별 알림 대상에서 제외
+                    flapping: health.flapping,
+                },
+                maintenance: maintenance.clone(),
+            });
+        }
+        match flapping {
+            Some(true) => events.push(HealthEvent {
+                target: target.name.clone(),
+                kind: HealthEventKind::FlappingStarted {
+                    changes: health.changes.len(),
+                    window: config.get_target_flap_window(target),
+                },
+                maintenance,
+            }),
+            Some(false) => events.push(HealthEvent {
+                target: target.name.clone(),
+                kind: HealthEventKind::FlappingStopped { state: health.state },
+                maintenance,
+            }),
+            None => {}
+        }
+        events
+    }
+
+    /// 상위 대상의 장애로 대상을 Unreachable로 표시하고, 상태가 바뀌었으면 이벤트를 반환합니다.
+    pub fn record_unreachable(&mut self, config: &Config, target: &NetworkTarget, root_cause: &str) -> Option<HealthEvent> {
+        let from = self.targets.entry(target.name.clone()).or_default().mark_unreachable()?;
+        Some(HealthEvent {
+            target: target.name.clone(),
+            kind: HealthEventKind::Unreachable {
+                from,
+                root_cause: root_cause.to_string(),
+            },
+            maintenance: maintenance_name(config, target),
+        })
+    }
+
+    /// `depends_on`을 따라 가까운 상위 대상부터 올라가며 Down 상태인 첫 대상(근본 원인)을 찾습니다.
+    pub fn root_cause(&self, config: &Config, target: &NetworkTarget) -> Option<String> {
+        self.find_ancestor(config, target, &[HealthState::Down])
+    }
+
+    /// 가까운 상위 대상부터 올라가며 상태가 `states` 중 하나인 첫 대상을 찾습니다.
+    fn find_ancestor(&self, config: &Config, target: &NetworkTarget, states: &[HealthState]) -> Option<String> {
+        let mut visited = HashSet::new();
+        let mut queue: VecDeque<&str> = target.depends_on.iter().map(String::as_str).collect();
+        while let Some(name) = queue.pop_front() {
+            if !visited.insert(name) {
+                continue;
+            }
+            if states.contains(&self.state(name)) {
+                return Some(name.to_string());
+            }
+            if let Some(parent) = config.targets.iter().find(|t| t.name == name) {
+                queue.extend(parent.depends_on.iter().map(String::as_str));
+            }
+        }
+        None
+    }
+
+    pub fn state(&self, name: &str) -> HealthState {
+        self.targets.get(name).map_or(HealthState::Unknown, |health| health.state)
+    }
+
+    pub fn is_flapping(&self, name: &str) -> bool {
+        self.targets.get(name).is_some_and(|health| health.flapping)
+    }
+
+    /// 설정에서 없어진 대상의 상태를 제거합니다.
+    pub fn retain(&mut self, config: &Config) {
+        self.targets.retain(|name, _| config.targets.iter().any(|t| t.name == *name));
+    }
+}
+
+/// 지금 대상이 들어 있는 점검 시간 이름
+fn maintenance_name(config: &Config, target: &NetworkTarget) -> Option<String> {
+    config.active_maintenance(target, &Local::now()).map(|window| window.name.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::test_support::{self, target};
+
+    /// 세 번 실패하면 Down이 되는 설정
+    fn config(targets: Vec<NetworkTarget>) -> Config {
+        test_support::config(targets, 3)
+    }
+
+    #[test]
+    fn failures_go_down_only_at_fail_threshold() {
+        let mut health = TargetHealth::default();
+        assert_eq!(health.record(false, 3, 2), Some(HealthState::Degraded));
+        assert_eq!(health.record(false, 3, 2), None);
+        assert_eq!(health.record(false, 3, 2), Some(HealthState::Down));
+        assert_eq!(health.consecutive_failures, 3);
+        assert_eq!(health.record(false, 3, 2), None);
+    }
+
+    #[test]
+    fn success_before_threshold_returns_to_up() {
+        let mut health = TargetHealth::default();
+        assert_eq!(health.record(true, 3, 2), Some(HealthState::Up));
+        assert_eq!(health.record(false, 3, 2), Some(HealthState::Degraded));
+        assert_eq!(health.record(true, 3, 2), Some(HealthState::Up));
+    }
+
+    #[test]
+    fn down_recovers_after_recover_threshold() {
+        let mut health = TargetHealth { state: HealthState::Down, ..TargetHealth::default() };
+        assert_eq!(health.record(true, 3, 2), Some(HealthState::Recovering));
+        // Recovering 중 실패하면 바로 Down으로 돌아감
+        assert_eq!(health.record(false, 3, 2), Some(HealthState::Down));
+        assert_eq!(health.record(true, 3, 2), Some(HealthState::Recovering));
+        assert_eq!(health.record(true, 3, 2), Some(HealthState::Up));
+    }
+
+    #[test]
+    fn unreachable_resets_counters() {
+        let mut health = TargetHealth::default();
+        health.record(false, 3, 2);
+        health.record(false, 3, 2);
+        assert_eq!(health.mark_unreachable(), Some(HealthState::Degraded));
+        assert_eq!(health.mark_unreachable(), None);
+        assert_eq!(health.consecutive_failures, 0);
+        // 상위 대상이 복구된 뒤에는 실패를 처음부터 다시 셈
+        assert_eq!(health.record(false, 3, 2), Some(HealthState::Degraded));
+        assert_eq!(health.record(true, 3, 2), Some(HealthState::Up));
+    }
+
+    #[test]
+    fn flapping_starts_at_threshold_and_stops_at_half() {
+        let window = Duration::from_secs(300);
+        let now = Instant::now();
+        let mut health = TargetHealth::default();
+        health.changes.extend([now; 3]);
+        assert_eq!(health.update_flapping(now, window, 4), None);
+        health.changes.push_back(now);
+        assert_eq!(health.update_flapping(now, window, 4), Some(true));
+
+        // threshold/2 = 2 보다 많으면 플래핑 유지
+        health.changes.pop_front();
+        assert_eq!(health.update_flapping(now, window, 4), None);
+        health.changes.pop_front();
+        assert_eq!(health.update_flapping(now, window, 4), Some(false));
+    }
+
+    #[test]
+    fn flapping_drops_changes_outside_window() {
+        let window = Duration::from_secs(10);
+        let start = Instant::now();
+        let mut health = TargetHealth::default();
+        health.changes.extend([start; 4]);
+        assert_eq!(health.update_flapping(start, window, 4), Some(true));
+        assert_eq!(health.update_flapping(start + Duration::from_secs(11), window, 4), Some(false));
+        assert!(health.changes.is_empty());
+    }
+
+    #[test]
+    fn zero_flap_threshold_disables_detection() {
+        let now = Instant::now();
+        let mut health = TargetHealth::default();
+        health.changes.extend([now; 10]);
+        assert_eq!(health.update_flapping(now, Duration::from_secs(300), 0), None);
+        assert!(!health.flapping);
+    }
+
+    /// app → (left, right) → router 형태의 다이아몬드 의존 관계
+    fn diamond() -> Config {
+        config(vec![
+            target("router", &[]),
+            target("left", &["router"]),
+            target("right", &["router"]),
+            target("app", &["left", "right"]),
+        ])
+    }
+
+    fn fail_until_down(tracker: &mut HealthTracker, config: &Config, name: &str) {
+        let target = config.targets.iter().find(|t| t.name == name).unwrap().clone();
+        for _ in 0..3 {
+            tracker.record(config, &target, false);
+        }
+        assert_eq!(tracker.state(name), HealthState::Down);
+    }
+
+    #[test]
+    fn root_cause_walks_diamond_to_shared_parent() {
+        let config = diamond();
+        let mut tracker = HealthTracker::default();
+        let app = config.targets[3].clone();
+        assert_eq!(tracker.root_cause(&config, &app), None);
+
+        fail_until_down(&mut tracker, &config, "router");
+        assert_eq!(tracker.root_cause(&config, &app), Some("router".to_string()));
+
+        // 가까운 상위 대상이 먼저 근본 원인이 됨
+        let mut tracker = HealthTracker::default();
+        fail_until_down(&mut tracker, &config, "right");
+        assert_eq!(tracker.root_cause(&config, &app), Some("right".to_string()));
+    }
+
+    #[test]
+    fn failure_under_failing_parent_is_unreachable() {
+        let config = diamond();
+        let mut tracker = HealthTracker::default();
+        let router = config.targets[0].clone();
+        let app = config.targets[3].clone();
+
+        // 상위 대상이 Degraded이기만 해도 자체 장애로 세지 않음
+        tracker.record(&config, &router, false);
+        let events = tracker.record(&config, &app, false);
+        assert_eq!(tracker.state("app"), HealthState::Unreachable);
+        assert_eq!(
+            events[0].kind,
+            HealthEventKind::Unreachable { from: HealthState::Unknown, root_cause: "router".to_string() }
+        );
+        assert!(!events[0].should_notify());
+        assert!(tracker.record(&config, &app, false).is_empty());
+
+        // 상위 대상이 복구되면 성공 한 번으로 Up
+        tracker.record(&config, &router, true);
+        tracker.record(&config, &app, true);
+        assert_eq!(tracker.state("app"), HealthState::Up);
+    }
+
+    #[test]
+    fn flapping_transitions_are_not_notified() {
+        let mut config = config(vec![target("a", &[])]);
+        config.fail_threshold = Some(1);
+        config.recover_threshold = Some(1);
+        config.flap_threshold = Some(4);
+        let a = config.targets[0].clone();
+        let mut tracker = HealthTracker::default();
+
+        tracker.record(&config, &a, true);
+        let mut events = Vec::new();
+        for success in [false, true, false, true] {
+            events.extend(tracker.record(&config, &a, success));
+        }
+        assert!(tracker.is_flapping("a"));
+        let started = events
+            .iter()
+            .position(|e| matches!(e.kind, HealthEventKind::FlappingStarted { .. }))
+            .unwrap();
+        assert!(events[started].should_notify());
+        // 플래핑을 시작하게 만든 전이와 이후 전이는 알림 대상이 아님
+        assert!(!events[started - 1].should_notify());
+        assert!(tracker.record(&config, &a, false).iter().all(|e| !e.should_notify()));
+    }
+}
@@ -1,36 +1,61 @@
-use crate::config::Config;
-use crate::network;
+pub mod escalation;
+pub mod health;
+pub mod outage;
+pub mod scheduler;
+pub mod verify;
+
+use crate::config::{Config, NetworkTarget, RecoveryAction, RecoveryGroup};
+use crate::network::events::LinkEvent;
+use crate::network::recovery::{Invocation, RunOptions};
+use crate::network::{self, AddressPing, ConnectionTiming, ExecutionResult, RecoveryPlan, TemplateContext};
+use crate::probe::{self, ProbeDetail, ProbeReport, ProbeStatus};
 use crate::utils::logging;
-use log::{error, info, warn};
+use chrono::Local;
+use log::{debug, error, info, log, warn, Level};
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use tokio::sync::{watch, Semaphore};
+use tokio::task::{self, JoinSet};
 use tokio::time;
 
+pub use escalation::{EscalationStep, EscalationTracker};
+pub use health::{HealthEvent, HealthEventKind, HealthState, HealthTracker};
+pub use scheduler::Scheduler;
+pub use verify::{verify_recovery, Verification};
+
 static MONITORING_ACTIVE: AtomicBool = AtomicBool::new(false);
 
+/// 인터페이스 이벤트 후 이어지는 이벤트를 기다리는 시간
+const LINK_EVENT_SETTLE: Duration = Duration::from_secs(1);
+
+/// 보관할 알림 명령 표준 출력/오류의 최대 크기 (바이트)
+const NOTIFICATION_OUTPUT_BYTES: usize = 4096;
+
+/// 스케줄러가 종료 요청을 확인하기 위해 깨어나는 최대 간격
+const SCHEDULER_MAX_SLEEP: Duration = Duration::from_secs(1);
+
 /// 네트워크 상태 확인 함수
 /// 스레드 간 안전한 에러 타입을 사용합니다.
 pub async fn check_status(config: &Config) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     info!("네트워크 상태 확인 시작");
     
+    let mut config = config.clone();
+    refresh_discovered_targets(&mut config);
+    let config = &config;
+    
     for target in &config.targets {
-        let result = network::ping_host(&target.address, config.get_target_timeout(target)).await;
-        match result {
-            Ok(rtt) => {
-                info!("대상 '{}' ({}) 응답 시간: {}ms", target.name, target.address, rtt.as_millis());
-            }
-            Err(e) => {
-                warn!("대상 '{}' ({}) 응답 없음: {}", target.name, target.address, e);
-            }
-        }
+        let report = probe::run_probe(config, target).await;
+        log_report(target, &report, 1, &maintenance_note(config, target));
         
         // 포트가 지정된 경우 포트 연결 테스트
         if let Some(port) = target.port {
             let result = network::check_port(&target.address, port, config.get_target_timeout(target)).await;
             match result {
-                Ok(_) => {
-                    info!("대상 '{}' ({}:{}) 포트 연결 성공", target.name, target.address, port);
+                Ok(timing) => {
+                    info!("대상 '{}' ({}:{}) 포트 연결 성공: {}", target.name, target.address, port, timing);
                 }
                 Err(e) => {
                     warn!("대상 '{}' ({}:{}) 포트 연결 실패: {}", target.name, target.address, port, e);
@@ -43,9 +68,174 @@ pub async fn check_status(config: &Config) -> Result<(), Box<dyn std::error::Err
     Ok(())
 }
 
+/// 기본 게이트웨이와 DNS 서버를 다시 검색해 자동 대상을 갱신합니다.
+/// `auto_discover`가 꺼져 있으면 자동 대상을 모두 제거합니다. 대상이 바뀌었으면 true를 반환합니다.
+pub fn refresh_discovered_targets(config: &mut Config) -> bool {
+    let discovered = if config.auto_discover {
+        network::discovery::discover()
+    } else {
+        network::discovery::DiscoveredServices::default()
+    };
+    
+    let changed = config.apply_discovered_targets(&discovered.gateways, &discovered.dns_servers);
+    if changed {
+        let names: Vec<&str> = config
+            .targets
+            .iter()
+            .filter(|t| t.auto_discovered)
+            .map(|t| t.name.as_str())
+            .collect();
+        info!("자동 검색 대상 갱신: [{}]", names.join(", "));
+    }
+    changed
+}
+
+/// 대상에 지금 적용 중인 점검 시간 이름
+fn maintenance_name(config: &Config, target: &NetworkTarget) -> Option<String> {
+    config.active_maintenance(target, &Local::now()).map(|window| window.name.clone())
+}
+
+/// 대상이 점검 시간 중이면 로그 끝에 붙일 표시를 만듭니다.
+fn maintenance_note(config: &Config, target: &NetworkTarget) -> String {
+    maintenance_name(config, target).map_or(String::new(), |window| format!(" [점검 중: {}]", window))
+}
+
+/// 확인 결과를 로그로 남깁니다. ICMP 결과는 주소 패밀리별로 한 줄씩 기록합니다.
+/// `maintenance_note`는 점검 시간 중일 때 줄 끝에 붙입니다.
+fn log_report(target: &NetworkTarget, report: &ProbeReport, attempt: u8, maintenance_note: &str) {
+    let retry_note = if attempt > 1 {
+        format!("재시도 #{} ", attempt)
+    } else {
+        String::new()
+    };
+
+    match &report.detail {
+        ProbeDetail::Icmp(results) => {
+            for ping in results {
+                match &ping.result {
+                    Ok(stats) => info!("대상 '{}' ({}) {}응답: {}{}", 
+                                       target.name, describe_address(target, ping), retry_note, stats, maintenance_note),
+                    Err(e) => warn!("대상 '{}' ({}) {} 응답 없음: {}{}", 
+                                    target.name, describe_address(target, ping), ping.family_label(), e, maintenance_note),
+                }
+            }
+        }
+        _ => {
+            let kind = probe::probe_kind(target);
+            let timing_note = match &report.detail {
+                ProbeDetail::Timing(timing) => format!(" [{}]", timing),
+                _ => String::new(),
+            };
+            match report.status {
+                ProbeStatus::Warning => warn!("대상 '{}' [{}] {}경고: {}{}{}", 
+                                              target.name, kind, retry_note, report, timing_note, maintenance_note),
+                ProbeStatus::Ok => info!("대상 '{}' [{}] {}응답: {}{}{}", 
+                                         target.name, kind, retry_note, report, timing_note, maintenance_note),
+                ProbeStatus::Failed => warn!("대상 '{}' [{}] 실패: {}{}{}", 
+                                             target.name, kind, report, timing_note, maintenance_note),
+            }
+        }
+    }
+}
+
+/// 로그에 표시할 대상 주소를 만듭니다. 호스트 이름이면 해석된 주소를 함께 표시합니다.
+fn describe_address(target: &NetworkTarget, ping: &AddressPing) -> String {
+    if target.address == ping.address.to_string() {
+        target.address.clone()
+    } else {
+        format!("{} → {}", target.address, ping.address)
+    }
+}
+
+/// 대상 하나의 확인 결과입니다.
+#[derive(Debug, Clone)]
+pub struct TargetCheck {
+    pub report: ProbeReport,
+    /// 대상에 port가 지정되어 있으면 포트 연결 결과
+    pub port: Option<Result<ConnectionTiming, String>>,
+}
+
+impl TargetCheck {
+    /// 확인 방식과 (지정되어 있으면) 포트 연결이 모두 성공했는지 여부
+    pub fn is_success(&self) -> bool {
+        self.report.is_success() && self.port.as_ref().is_none_or(|port| port.is_ok())
+    }
+}
+
+/// 대상 하나의 확인이 끝났을 때의 상태입니다.
+#[derive(Debug, Clone)]
+#[cfg_attr(not(feature = "gui"), allow(dead_code))]
+pub struct TargetUpdate {
+    pub target: String,
+    /// 확인 결과 (상위 대상 장애로 확인을 생략했으면 None)
+    pub check: Option<TargetCheck>,
+    pub health: HealthState,
+    pub flapping: bool,
+    /// 이 대상을 unreachable로 만든 상위 대상
+    pub root_cause: Option<String>,
+    /// 적용 중인 점검 시간 이름
+    pub maintenance: Option<String>,
+}
+
+/// 모니터링 루프가 관찰하는 쪽(GUI)에 보내는 이벤트입니다.
+#[derive(Debug, Clone)]
+#[cfg_attr(not(feature = "gui"), allow(dead_code))]
+pub enum MonitorEvent {
+    Target(Box<TargetUpdate>),
+    /// 상태 이벤트 (로그 기록과 알림은 모니터링 루프에서 처리함)
+    Health(HealthEvent),
+    /// 자동 검색 대상이 바뀌어 새로 정해진 전체 대상 목록
+    TargetsChanged(Vec<NetworkTarget>),
+    /// 복구 작업 실행 결과 (명령을 실행하지 못했거나 실행한 명령이 실패했으면 verification은 None)
+    Recovery {
+        group: String,
+        action: String,
+        result: ExecutionResult,
+        verification: Option<Verification>,
+    },
+}
+
+/// 모니터링 루프를 다른 스레드(GUI)에서 멈추고, 설정을 바꾸고, 결과를 받기 위한 연결입니다.
+/// 기본값은 CLI/서비스 모드와 같이 Ctrl+C로만 종료하고 이벤트를 보내지 않습니다.
+#[derive(Default)]
+pub struct MonitorLink {
+    /// false로 바꾸면 모니터링을 종료 (지정하면 Ctrl+C 핸들러를 등록하지 않음)
+    pub running: Option<Arc<AtomicBool>>,
+    /// 확인 결과와 상태 이벤트를 받을 채널
+    pub events: Option<UnboundedSender<MonitorEvent>>,
+    /// 새 설정을 받을 채널. 자동 검색 대상은 모니터링 루프에서 다시 채웁니다.
+    pub config_updates: Option<watch::Receiver<Config>>,
+}
+
+impl MonitorLink {
+    fn publish(&self, event: MonitorEvent) {
+        if let Some(events) = &self.events {
+            // 받는 쪽이 닫혔으면 버림
+            let _ = events.send(event);
+        }
+    }
+
+    /// 아직 적용하지 않은 새 설정
+    fn take_config_update(&mut self) -> Option<Config> {
+        let updates = self.config_updates.as_mut()?;
+        if !updates.has_changed().unwrap_or(false) {
+            return None;
+        }
+        Some(updates.borrow_and_update().clone())
+    }
+}
+
 /// 네트워크 모니터링 시작 함수
 /// 스레드 간 안전한 에러 타입을 사용합니다.
 pub async fn start_monitoring(config: Config) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    start_monitoring_with(config, MonitorLink::default()).await
+}
+
+/// `link`로 종료 요청, 설정 변경, 결과 전달을 연결해 모니터링을 시작합니다 (GUI에서 사용).
+pub async fn start_monitoring_with(
+    config: Config,
+    mut link: MonitorLink,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     // 이미 모니터링 중인지 확인
     if MONITORING_ACTIVE.swap(true, Ordering::SeqCst) {
         warn!("이미 모니터링이 실행 중입니다");
@@ -59,68 +249,182 @@ pub async fn start_monitoring(config: Config) -> Result<(), Box<dyn std::error::
         logging::setup_file_logger(log_file)?;
     }
     
-    // Ctrl+C 핸들러 설정
-    let running = Arc::new(AtomicBool::new(true));
-    let r = running.clone();
-    ctrlc::set_handler(move || {
-        info!("Ctrl+C 신호 감지, 모니터링 종료 중...");
-        r.store(false, Ordering::SeqCst);
-    })?;
+    // Ctrl+C 핸들러 설정 (종료 플래그를 받았으면 종료는 호출자가 처리)
+    let running = match &link.running {
+        Some(running) => running.clone(),
+        None => {
+            let running = Arc::new(AtomicBool::new(true));
+            let r = running.clone();
+            // 호출자(main)가 이미 핸들러를 등록했으면 종료는 호출자가 처리
+            if let Err(e) = ctrlc::set_handler(move || {
+                info!("Ctrl+C 신호 감지, 모니터링 종료 중...");
+                r.store(false, Ordering::SeqCst);
+            }) {
+                debug!("Ctrl+C 핸들러를 등록하지 않음: {}", e);
+            }
+            running
+        }
+    };
     
-    // 모니터링 루프
-    let interval = Duration::from_secs(config.check_interval_sec);
-    let config = Arc::new(config);
+    // 모니터링 루프: 대상마다 자신의 확인 주기에 맞춰 확인
+    let semaphore = Arc::new(Semaphore::new(config.get_max_concurrent_probes()));
+    let mut config = Arc::new(config);
+    let mut link_events = subscribe_link_events();
+    let mut scheduler = Scheduler::default();
+    let mut probes = JoinSet::new();
+    let mut running_probes: HashMap<task::Id, String> = HashMap::new();
+    let mut health = HealthTracker::default();
+    // 대상별 마지막 실패 시각과 복구 그룹별 마지막 복구 시각 (down 상태로 남아 있는 동안 복구를 반복하기 위해 사용)
+    let mut last_failure: HashMap<String, Instant> = HashMap::new();
+    let mut last_recovery: HashMap<String, Instant> = HashMap::new();
+    let mut escalation = EscalationTracker::default();
+    // 실행 중인 복구 작업 (확인 루프를 멈추지 않도록 그룹마다 백그라운드 작업으로 실행)
+    let mut recoveries = JoinSet::new();
+    let mut running_recoveries: HashMap<task::Id, String> = HashMap::new();
+    // 실행 중인 알림 명령 (알림 창이 닫히거나 명령이 끝날 때까지 확인 루프를 멈추지 않도록 백그라운드로 실행)
+    let mut notifications = JoinSet::new();
+    let mut next_discovery = Instant::now();
     
     while running.load(Ordering::SeqCst) {
-        let mut all_targets_failed = true;
+        let now = Instant::now();
         
-        // 모든 대상 확인
-        for target in &config.targets {
-            let retry_count = config.get_target_retry_count(target);
-            let mut success = false;
+        // 바뀐 설정은 자동 검색 대상을 바로 다시 채워서 적용
+        if let Some(updated) = link.take_config_update() {
+            info!("바뀐 설정을 적용합니다");
+            config = Arc::new(updated);
+            next_discovery = now;
+        }
+        
+        // 게이트웨이/DNS 서버 변경 반영
+        if now >= next_discovery {
+            if refresh_discovered_targets(Arc::make_mut(&mut config)) {
+                link.publish(MonitorEvent::TargetsChanged(config.targets.clone()));
+            }
+            next_discovery = now + Duration::from_secs(config.check_interval_sec);
+        }
+        scheduler.sync(&config, now);
+        health.retain(&config);
+        last_failure.retain(|name, _| config.targets.iter().any(|t| t.name == *name));
+        
+        // 확인할 때가 된 대상을 시작 (동시 실행 수는 max_concurrent_probes로 제한)
+        for target in scheduler.take_due(&config, now) {
+            // 상위 대상이 down이면 확인하지 않고 unreachable로 보고
+            if let Some(root_cause) = health.root_cause(&config, target) {
+                debug!("대상 '{}' 확인 생략: 상위 대상 '{}' 장애", target.name, root_cause);
+                if let Some(event) = health.record_unreachable(&config, target, &root_cause) {
+                    handle_health_event(&config, &event, &mut notifications);
+                    link.publish(MonitorEvent::Health(event));
+                }
+                link.publish(MonitorEvent::Target(Box::new(TargetUpdate {
+                    target: target.name.clone(),
+                    check: None,
+                    health: health.state(&target.name),
+                    flapping: health.is_flapping(&target.name),
+                    root_cause: Some(root_cause),
+                    maintenance: maintenance_name(&config, target),
+                })));
+                scheduler.complete(&config, &target.name, now);
+                continue;
+            }
             
-            // 재시도 로직
-            for attempt in 1..=retry_count {
-                match network::ping_host(&target.address, config.get_target_timeout(target)).await {
-                    Ok(rtt) => {
-                        if attempt > 1 {
-                            info!("대상 '{}' ({}) 재시도 #{} 성공, 응답 시간: {}ms", 
-                                 target.name, target.address, attempt, rtt.as_millis());
-                        } else {
-                            info!("대상 '{}' ({}) 응답 시간: {}ms", 
-                                 target.name, target.address, rtt.as_millis());
+            let config = config.clone();
+            let target = target.clone();
+            let semaphore = semaphore.clone();
+            let name = target.name.clone();
+            let handle = probes.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                check_target(&config, &target).await
+            });
+            running_probes.insert(handle.id(), name);
+        }
+        
+        // 확인 완료, 다음 예약 시각, 인터페이스 이벤트 중 먼저 일어나는 것을 처리
+        // (종료 요청을 확인할 수 있도록 최대 SCHEDULER_MAX_SLEEP만큼만 대기)
+        let wakeup = scheduler
+            .next_wakeup()
+            .map_or(now + SCHEDULER_MAX_SLEEP, |at| at.min(now + SCHEDULER_MAX_SLEEP));
+        tokio::select! {
+            Some(result) = probes.join_next_with_id(), if !probes.is_empty() => {
+                let (id, check) = match result {
+                    Ok((id, check)) => (id, Some(check)),
+                    Err(e) => {
+                        error!("대상 확인 작업 실패: {}", e);
+                        (e.id(), None)
+                    }
+                };
+                if let Some(name) = running_probes.remove(&id) {
+                    let success = check.as_ref().is_some_and(TargetCheck::is_success);
+                    scheduler.complete(&config, &name, Instant::now());
+                    if let Some(target) = config.targets.iter().find(|t| t.name == name) {
+                        for event in health.record(&config, target, success) {
+                            handle_health_event(&config, &event, &mut notifications);
+                            link.publish(MonitorEvent::Health(event));
                         }
-                        success = true;
-                        break;
+                        link.publish(MonitorEvent::Target(Box::new(TargetUpdate {
+                            target: name.clone(),
+                            check,
+                            health: health.state(&name),
+                            flapping: health.is_flapping(&name),
+                            root_cause: None,
+                            maintenance: maintenance_name(&config, target),
+                        })));
                     }
+                    if success {
+                        last_failure.remove(&name);
+                    } else {
+                        last_failure.insert(name, Instant::now());
+                    }
+                }
+                
+                // 복구 그룹별 장애 정책 확인
+                let mut recovery = RecoveryState {
+                    last_recovery: &last_recovery,
+                    escalation: &mut escalation,
+                    tasks: &mut recoveries,
+                    running: &mut running_recoveries,
+                    notifications: &mut notifications,
+                };
+                check_outages(&config, &health, &last_failure, &mut recovery);
+            }
+            Some(result) = recoveries.join_next_with_id(), if !recoveries.is_empty() => {
+                let (id, report) = match result {
+                    Ok((id, report)) => (id, Some(report)),
                     Err(e) => {
-                        if attempt == retry_count {
-                            error!("대상 '{}' ({}) 모든 재시도 실패: {}", 
-                                  target.name, target.address, e);
-                        } else {
-                            warn!("대상 '{}' ({}) 재시도 #{} 실패: {}", 
-                                 target.name, target.address, attempt, e);
-                            time::sleep(Duration::from_millis(500)).await;
-                        }
+                        error!("복구 작업 실행 실패: {}", e);
+                        (e.id(), None)
                     }
+                };
+                if let Some(group) = running_recoveries.remove(&id) {
+                    if let Some(RecoveryReport { action, outcome }) = report {
+                        finish_recovery(&mut escalation, &link, &group, &action, outcome);
+                    }
+                    // 복구 작업이 끝난 뒤 다시 실패해야 같은 그룹의 복구를 반복
+                    last_recovery.insert(group, Instant::now());
                 }
             }
-            
-            if success {
-                all_targets_failed = false;
+            Some(result) = notifications.join_next(), if !notifications.is_empty() => {
+                if let Err(e) = result {
+                    error!("알림 작업 실행 실패: {}", e);
+                }
+            }
+            _ = time::sleep_until(wakeup.into()) => {}
+            _ = recv_link_event(&mut link_events) => {
+                // 인터페이스 이벤트가 발생하면 모든 대상을 즉시 다시 확인
+                settle_link_events(&mut link_events).await;
+                scheduler.trigger_all(Instant::now());
             }
         }
-        
-        // 모든 대상이 실패했을 경우 복구 작업 수행
-        if all_targets_failed && !config.recovery_actions.is_empty() {
-            error!("모든 네트워크 대상 연결 실패, 복구 작업 시작");
-            perform_recovery_actions(&config).await?;
-        }
-        
-        // 다음 체크까지 대기
-        time::sleep(interval).await;
     }
     
+    // 실행 중인 복구 작업은 중간에 멈추지 않고 끝까지 실행되도록 둠
+    if !running_recoveries.is_empty() {
+        let groups: Vec<&str> = running_recoveries.values().map(String::as_str).collect();
+        info!("실행 중인 복구 작업이 끝날 때까지 기다립니다: {}", groups.join(", "));
+        while recoveries.join_next().await.is_some() {}
+    }
+    // 실행 중인 알림 명령도 끝까지 실행 (notification_timeout_ms가 지나면 종료됨)
+    while notifications.join_next().await.is_some() {}
+    
     // 모니터링 종료
     MONITORING_ACTIVE.store(false, Ordering::SeqCst);
     info!("네트워크 모니터링 종료");
@@ -128,54 +432,440 @@ pub async fn start_monitoring(config: Config) -> Result<(), Box<dyn std::error::
     Ok(())
 }
 
-/// 복구 작업 수행 함수
-async fn perform_recovery_actions(config: &Arc<Config>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    for action in &config.recovery_actions {
-        info!("복구 작업 '{}' 실행 중", action.name);
-        
-        match network::execute_command(&action.command).await {
-            Ok(output) => {
-                info!("복구 작업 '{}' 성공: {}", action.name, output);
-                
-                // 대기 시간이 지정된 경우 대기
-                if let Some(wait_ms) = action.wait_after_ms {
-                    info!("복구 작업 후 {}ms 대기 중", wait_ms);
-                    time::sleep(Duration::from_millis(wait_ms)).await;
-                }
-                
-                // 복구 후 네트워크 상태 다시 확인
-                let recovered = check_recovery_success(config).await;
-                if recovered {
-                    info!("네트워크 연결이 복구되었습니다");
-                    
-                    // 알림 기능이 활성화된 경우 알림 전송
-                    if config.notification_enabled {
-                        if let Some(cmd) = &config.notification_command {
-                            match network::execute_command(cmd).await {
-                                Ok(_) => info!("복구 알림 전송 성공"),
-                                Err(e) => warn!("복구 알림 전송 실패: {}", e),
-                            }
-                        }
-                    }
-                    
-                    return Ok(());
+/// 복구 작업 하나를 수행한 결과입니다.
+enum RecoveryOutcome {
+    /// 모의 실행(dry-run)으로 실행하지 않음
+    Skipped,
+    /// 명령 생성 실패 (None) 또는 실행 실패
+    Failed(Option<ExecutionResult>),
+    /// 실행 결과와 실행 후 연결 확인 결과
+    Verified(ExecutionResult, Verification),
+}
+
+/// 백그라운드에서 끝난 복구 작업의 결과입니다.
+struct RecoveryReport {
+    action: RecoveryAction,
+    outcome: RecoveryOutcome,
+}
+
+/// 장애 판단에 필요한 복구 이력과 실행 중인 복구 작업입니다.
+struct RecoveryState<'a> {
+    /// 복구 그룹별 마지막 복구 작업이 끝난 시각
+    last_recovery: &'a HashMap<String, Instant>,
+    escalation: &'a mut EscalationTracker,
+    tasks: &'a mut JoinSet<RecoveryReport>,
+    /// 작업 ID별 복구 그룹 이름
+    running: &'a mut HashMap<task::Id, String>,
+    /// 실행 중인 알림 명령
+    notifications: &'a mut JoinSet<()>,
+}
+
+/// 복구 그룹마다 장애 판단 정책을 확인하고, 장애이면 복구 단계(escalation ladder)에 따라 복구 작업을 하나 시작합니다.
+/// 복구 작업은 백그라운드에서 실행되며, 그 그룹의 복구 작업이 끝날 때까지 그룹의 장애 판단을 미룹니다.
+/// 같은 그룹의 복구는 근본 원인(down) 대상이 모두 마지막 복구 이후 다시 실패했을 때만 반복하며,
+/// 그룹에 플래핑 중인 대상이 있거나 근본 원인 대상이 모두 점검 시간 중이면 복구하지 않습니다.
+fn check_outages(
+    config: &Arc<Config>,
+    health: &HealthTracker,
+    last_failure: &HashMap<String, Instant>,
+    recovery: &mut RecoveryState<'_>,
+) {
+    for group in config.get_recovery_groups() {
+        if group.actions.is_empty() || recovery.running.values().any(|running| *running == group.name) {
+            continue;
+        }
+        let escalation = &mut *recovery.escalation;
+        let now = Instant::now();
+        let Some(outage) = outage::evaluate(config, &group, health) else {
+            if let Some(resolution) = escalation.resolve(&group.name, now) {
+                match resolution.fixed_by {
+                    Some(action) => info!(
+                        "복구 그룹 '{}' 장애 해소 (장애 시간 {}초, 복구 작업 '{}')",
+                        group.name,
+                        resolution.duration.as_secs(),
+                        action
+                    ),
+                    None => info!(
+                        "복구 그룹 '{}' 장애 해소 (장애 시간 {}초)",
+                        group.name,
+                        resolution.duration.as_secs()
+                    ),
                 }
             }
-            Err(e) => {
-                error!("복구 작업 '{}' 실패: {}", action.name, e);
+            continue;
+        };
+        // 점검 중에 시작된 장애는 점검이 끝난 뒤부터 장애 시간을 셈
+        if let Some(windows) = maintenance_windows(config, &outage.root_causes) {
+            debug!(
+                "복구 그룹 '{}' 장애 판단: {} (점검 중: {}), 복구 작업과 알림을 생략합니다",
+                group.name,
+                outage.reason,
+                windows.join(", ")
+            );
+            continue;
+        }
+        escalation.begin(&group.name, now);
+        
+        let failed_again = outage.root_causes.iter().all(|t| {
+            match (last_failure.get(&t.name), recovery.last_recovery.get(&group.name)) {
+                (Some(failed), Some(recovered)) => failed > recovered,
+                (Some(_), None) => true,
+                (None, _) => false,
+            }
+        });
+        if !failed_again {
+            continue;
+        }
+        if config.targets.iter().any(|t| group.includes(t) && health.is_flapping(&t.name)) {
+            debug!("복구 그룹 '{}'에 플래핑 중인 대상이 있어 복구 작업을 건너뜁니다", group.name);
+            continue;
+        }
+        
+        let root_causes: Vec<&str> = outage.root_causes.iter().map(|t| t.name.as_str()).collect();
+        match escalation.next_step(&group, now) {
+            EscalationStep::Wait => {}
+            EscalationStep::GiveUp => {
+                error!(
+                    "복구 그룹 '{}' 복구 단계를 모두 소진했습니다. 장애가 해소될 때까지 복구 작업을 중단합니다 (근본 원인: {})",
+                    group.name,
+                    root_causes.join(", ")
+                );
+                let duration = escalation.outage_duration(&group.name, now);
+                let context = template_context(config, &group.name, "gave_up", duration);
+                spawn_notification(config, context, "down", recovery.notifications);
+            }
+            EscalationStep::Run { action, rung } => {
+                let duration = escalation.outage_duration(&group.name, now).unwrap_or_default();
+                error!(
+                    "복구 그룹 '{}' 장애 판단: {} (근본 원인: {}, 장애 시간 {}초), 복구 단계 {}/{} 시작",
+                    group.name,
+                    outage.reason,
+                    root_causes.join(", "),
+                    duration.as_secs(),
+                    rung,
+                    group.actions.len()
+                );
+                escalation.record_run(&group.name, action, now);
+                let mut context = template_context(config, root_causes[0], "outage", Some(duration));
+                context.interface = network::default_route_interface();
+                spawn_recovery(config, &group, action, context, recovery);
             }
         }
     }
+}
+
+/// 복구 작업을 백그라운드에서 시작합니다. 결과는 확인 루프에서 `finish_recovery`로 반영합니다.
+fn spawn_recovery(
+    config: &Arc<Config>,
+    group: &RecoveryGroup,
+    action: &RecoveryAction,
+    context: TemplateContext,
+    recovery: &mut RecoveryState<'_>,
+) {
+    let config = config.clone();
+    let group_name = group.name.clone();
+    let action = action.clone();
+    let handle = recovery.tasks.spawn(async move {
+        let outcome = perform_recovery_action(&config, &group_name, &action, &context).await;
+        RecoveryReport { action, outcome }
+    });
+    recovery.running.insert(handle.id(), group.name.clone());
+}
+
+/// 끝난 복구 작업의 결과를 복구 단계 이력에 반영하고 `link`로 전달합니다. 실패한 단계는 다음 확인 때 다음 단계로 올라갑니다.
+fn finish_recovery(
+    escalation: &mut EscalationTracker,
+    link: &MonitorLink,
+    group: &str,
+    action: &RecoveryAction,
+    outcome: RecoveryOutcome,
+) {
+    let (result, verification) = match outcome {
+        RecoveryOutcome::Skipped => return,
+        RecoveryOutcome::Failed(result) => {
+            escalation.record_failure(group, action);
+            // 명령을 만들지 못했으면 실행 결과가 없음
+            let Some(result) = result else {
+                return;
+            };
+            (result, None)
+        }
+        RecoveryOutcome::Verified(result, verification) => {
+            let outcomes = escalation.record_outcome(group, action, verification.success);
+            info!(
+                "복구 작업 '{}' 누적 확인 결과: {}회 중 {}회 복구",
+                action.name, outcomes.verified_runs, outcomes.fixes
+            );
+            (result, Some(verification))
+        }
+    };
+    link.publish(MonitorEvent::Recovery {
+        group: group.to_string(),
+        action: action.name.clone(),
+        result,
+        verification,
+    });
+}
+
+/// 대상이 모두 점검 시간 중이면 점검 시간 이름 목록을 반환합니다.
+fn maintenance_windows<'a>(config: &'a Config, targets: &[&NetworkTarget]) -> Option<Vec<&'a str>> {
+    let now = Local::now();
+    let mut windows = Vec::new();
+    for target in targets {
+        let name = config.active_maintenance(target, &now)?.name.as_str();
+        if !windows.contains(&name) {
+            windows.push(name);
+        }
+    }
+    Some(windows)
+}
+
+/// 대상 하나를 확인 방식으로 확인하고, port가 지정되어 있으면 포트 연결도 확인합니다.
+async fn check_target(config: &Config, target: &NetworkTarget) -> TargetCheck {
+    let maintenance_note = maintenance_note(config, target);
+    let report = probe_with_retries(config, target, &maintenance_note).await;
+    let port = match target.port {
+        Some(port) => {
+            let result = network::check_port(&target.address, port, config.get_target_timeout(target)).await;
+            if let Err(e) = &result {
+                warn!("대상 '{}' ({}:{}) 포트 연결 실패: {}{}", target.name, target.address, port, e, maintenance_note);
+            }
+            Some(result.map_err(|e| e.to_string()))
+        }
+        None => None,
+    };
+    TargetCheck { report, port }
+}
+
+/// 대상 하나를 재시도 횟수만큼 확인하고 마지막 결과를 반환합니다. 재시도는 대상마다 독립적으로 진행됩니다.
+async fn probe_with_retries(config: &Config, target: &NetworkTarget, maintenance_note: &str) -> ProbeReport {
+    let retry_count = config.get_target_retry_count(target).max(1);
+    let mut attempt = 1;
     
-    error!("모든 복구 작업이 실패했습니다");
-    Ok(())
+    loop {
+        let report = probe::run_probe(config, target).await;
+        if report.is_success() {
+            log_report(target, &report, attempt, maintenance_note);
+            return report;
+        }
+        
+        if attempt >= retry_count {
+            error!("대상 '{}' ({}) 모든 재시도 실패: {}{}", 
+                  target.name, target.address, report, maintenance_note);
+            return report;
+        }
+        warn!("대상 '{}' ({}) 재시도 #{} 실패: {}{}", 
+             target.name, target.address, attempt, report, maintenance_note);
+        time::sleep(Duration::from_millis(500)).await;
+        attempt += 1;
+    }
+}
+
+/// 인터페이스 링크/주소 이벤트 구독을 시작합니다. 실패하면 주기적인 확인만 수행합니다.
+pub fn subscribe_link_events() -> Option<UnboundedReceiver<LinkEvent>> {
+    match network::events::subscribe() {
+        Ok(events) => Some(events),
+        Err(e) => {
+            warn!("인터페이스 이벤트 구독 실패, 주기적인 확인만 수행합니다: {}", e);
+            None
+        }
+    }
+}
+
+/// 인터페이스 이벤트 하나를 기다립니다. 구독이 없거나 종료되면 반환하지 않습니다.
+async fn recv_link_event(events: &mut Option<UnboundedReceiver<LinkEvent>>) {
+    let Some(receiver) = events.as_mut() else {
+        return std::future::pending().await;
+    };
+    match receiver.recv().await {
+        Some(event) => info!("인터페이스 이벤트: {}", event),
+        None => {
+            warn!("인터페이스 이벤트 구독이 종료되었습니다");
+            *events = None;
+            std::future::pending().await
+        }
+    }
+}
+
+/// 링크 연결 직후 주소 할당처럼 이어지는 이벤트를 모아서 한 번만 확인하도록 잠시 기다립니다.
+async fn settle_link_events(events: &mut Option<UnboundedReceiver<LinkEvent>>) {
+    time::sleep(LINK_EVENT_SETTLE).await;
+    if let Some(receiver) = events.as_mut() {
+        while let Ok(event) = receiver.try_recv() {
+            info!("인터페이스 이벤트: {}", event);
+        }
+    }
+    info!("인터페이스 이벤트로 즉시 확인 시작");
+}
+
+/// 상태 이벤트를 기록하고, 장애 발생/복구 또는 플래핑 시작/종료 시 알림 명령을 `notifications`에서 백그라운드로 실행합니다.
+/// 플래핑 중의 개별 상태 전이와 점검 시간 중의 이벤트는 알림을 보내지 않습니다. 알림 명령에는 `NETWORK_MONITOR_TARGET`, `NETWORK_MONITOR_STATE`,
+/// `NETWORK_MONITOR_PREVIOUS_STATE` 환경 변수가 전달됩니다.
+fn handle_health_event(config: &Arc<Config>, event: &HealthEvent, notifications: &mut JoinSet<()>) {
+    match &event.kind {
+        HealthEventKind::Transition { to: HealthState::Down, .. } => error!("{}", event),
+        HealthEventKind::Transition { to: HealthState::Degraded, .. }
+        | HealthEventKind::Unreachable { .. }
+        | HealthEventKind::FlappingStarted { .. } => {
+            warn!("{}", event)
+        }
+        _ => info!("{}", event),
+    }
+    
+    if !event.should_notify() {
+        return;
+    }
+    let context = template_context(config, &event.target, &event.state_label(), None);
+    spawn_notification(config, context, &event.previous_state_label(), notifications);
+}
+
+/// 알림 명령을 백그라운드에서 실행합니다.
+fn spawn_notification(config: &Arc<Config>, context: TemplateContext, previous: &str, notifications: &mut JoinSet<()>) {
+    let config = config.clone();
+    let previous = previous.to_string();
+    notifications.spawn(async move { send_notification(&config, &context, &previous).await });
+}
+
+/// 명령 템플릿에 넣을 값을 만듭니다. 대상이 설정에 있으면 주소도 채웁니다.
+/// 인터페이스는 조회 비용이 있으므로 필요한 곳에서 채웁니다.
+fn template_context(config: &Config, target: &str, event: &str, failure_duration: Option<Duration>) -> TemplateContext {
+    TemplateContext {
+        target_name: Some(target.to_string()),
+        target_address: config.targets.iter().find(|t| t.name == target).map(|t| t.address.clone()),
+        interface: None,
+        failure_duration,
+        event: Some(event.to_string()),
+    }
+}
+
+/// 알림 기능이 활성화된 경우 알림 명령을 실행합니다. `context`의 대상과 이벤트를 알림 대상과 상태로 사용합니다.
+async fn send_notification(config: &Config, context: &TemplateContext, previous: &str) {
+    if !config.notification_enabled {
+        return;
+    }
+    let Some(cmd) = &config.notification_command else {
+        return;
+    };
+    let target = context.target_name.as_deref().unwrap_or_default();
+    let state = context.event.as_deref().unwrap_or_default();
+    let shell = config.get_shell();
+    let mut context = context.clone();
+    if std::iter::once(cmd).chain(&config.notification_args).any(|part| part.contains("{interface}")) {
+        context.interface = network::default_route_interface();
+    }
+    let (cmd, args) = match network::template::render_command(shell, cmd, &config.notification_args, &context) {
+        Ok(rendered) => rendered,
+        Err(e) => {
+            warn!("'{}' {} 알림 명령 생성 실패: {}", target, state, e);
+            return;
+        }
+    };
+    
+    let options = RunOptions {
+        timeout: config.get_notification_timeout(),
+        max_output_bytes: NOTIFICATION_OUTPUT_BYTES,
+        accepted_exit_codes: Vec::new(),
+        envs: vec![
+            ("NETWORK_MONITOR_TARGET".to_string(), target.to_string()),
+            ("NETWORK_MONITOR_STATE".to_string(), state.to_string()),
+            ("NETWORK_MONITOR_PREVIOUS_STATE".to_string(), previous.to_string()),
+        ],
+    };
+    let result = Invocation::shell(shell, &cmd, &args).run(&options).await;
+    if result.success {
+        info!("'{}' {} 알림 전송 성공", target, state);
+    } else if result.stderr.trim().is_empty() {
+        warn!("'{}' {} 알림 전송 실패: {}", target, state, result);
+    } else {
+        warn!("'{}' {} 알림 전송 실패: {}: {}", target, state, result, result.stderr.trim());
+    }
 }
 
-/// 복구 성공 여부 확인 함수
-async fn check_recovery_success(config: &Arc<Config>) -> bool {
-    // 기본 대상으로 ping 테스트
-    match network::ping_host(&config.default_target, Duration::from_millis(config.ping_timeout_ms)).await {
-        Ok(_) => true,
-        Err(_) => false,
+/// 복구 작업 하나를 수행하고, 작업의 verify 설정에 따라 연결이 복구되었는지 확인합니다.
+/// 명령 실행에 실패하거나 모의 실행(dry-run)이면 확인하지 않습니다.
+async fn perform_recovery_action(
+    config: &Config,
+    group: &str,
+    action: &RecoveryAction,
+    context: &TemplateContext,
+) -> RecoveryOutcome {
+    let plan = match RecoveryPlan::new(config, action, context) {
+        Ok(plan) => plan,
+        Err(e) => {
+            error!("복구 작업 '{}' 명령 생성 실패: {}", action.name, e);
+            return RecoveryOutcome::Failed(None);
+        }
+    };
+    if action.is_dry_run() {
+        warn!("[dry-run] 복구 작업 '{}' 실행 생략 ({}): {}", action.name, action.kind, plan);
+        return RecoveryOutcome::Skipped;
+    }
+    info!("복구 작업 '{}' 실행 중: {}", action.name, plan);
+    
+    let result = plan.run(action).await;
+    log_command_output(action, &result);
+    if !result.success {
+        error!("복구 작업 '{}' 실패: {}", action.name, result);
+        return RecoveryOutcome::Failed(Some(result));
+    }
+    info!("복구 작업 '{}' 성공: {}", action.name, result);
+    
+    // 대기 시간이 지정된 경우 대기
+    if let Some(wait_ms) = action.wait_after_ms {
+        info!("복구 작업 후 {}ms 대기 중", wait_ms);
+        time::sleep(Duration::from_millis(wait_ms)).await;
+    }
+    
+    // 복구 후 네트워크 상태 다시 확인
+    let verification = verify_recovery(config, action).await;
+    if verification.success {
+        info!("복구 작업 '{}'로 연결이 복구되었습니다: {}", action.name, verification);
+        
+        // 알림 기능이 활성화된 경우 알림 전송
+        let context = template_context(config, group, "recovered", context.failure_duration);
+        send_notification(config, &context, "down").await;
+    } else {
+        warn!("복구 작업 '{}' 후에도 연결이 복구되지 않았습니다: {}", action.name, verification);
+    }
+    RecoveryOutcome::Verified(result, verification)
+}
+
+/// 복구 명령의 표준 출력/오류를 기록합니다. 출력은 max_output_bytes까지만 보관됩니다.
+/// 실패한 명령의 표준 오류는 경고로, 나머지는 디버그 로그로 남깁니다.
+fn log_command_output(action: &RecoveryAction, result: &ExecutionResult) {
+    for step in &result.steps {
+        let truncated = if step.truncated { " (잘림)" } else { "" };
+        if !step.stdout.trim().is_empty() {
+            debug!("복구 작업 '{}' 표준 출력{}: {}", action.name, truncated, step.stdout.trim());
+        }
+        if !step.stderr.trim().is_empty() {
+            let level = if step.success { Level::Debug } else { Level::Warn };
+            log!(level, "복구 작업 '{}' 표준 오류{}: {}", action.name, truncated, step.stderr.trim());
+        }
+    }
+}
+
+/// 여러 모듈의 테스트가 함께 쓰는 설정·대상 생성기
+#[cfg(test)]
+pub(crate) mod test_support {
+    use crate::config::{Config, NetworkTarget};
+
+    /// 문서용 주소(192.0.2.1)를 쓰는 테스트 대상
+    pub fn target(name: &str, depends_on: &[&str]) -> NetworkTarget {
+        NetworkTarget {
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            ..toml::from_str(&format!("name = \"{}\"\naddress = \"192.0.2.1\"", name)).unwrap()
+        }
+    }
+
+    /// 자동 검색을 끄고 `fail_threshold`번 연속 실패 시 Down, 2번 성공 시 Up이 되는 설정
+    pub fn config(targets: Vec<NetworkTarget>, fail_threshold: u32) -> Config {
+        Config {
+            targets,
+            fail_threshold: Some(fail_threshold),
+            recover_threshold: Some(2),
+            auto_discover: false,
+            ..Config::default()
+        }
     }
 }
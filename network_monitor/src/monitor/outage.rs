@@ -0,0 +1,192 @@
+use super::health::{HealthState, HealthTracker};
+use crate::config::{Config, NetworkTarget, OutagePolicy, RecoveryGroup};
+
+/// 복구 그룹의 정책에 따른 장애 판단 결과입니다.
+#[derive(Debug, Clone)]
+pub struct Outage<'a> {
+    /// 판단 근거 (로그용)
+    pub reason: String,
+    /// 그룹 안에서 down 상태인 대상 (근본 원인)
+    pub root_causes: Vec<&'a NetworkTarget>,
+}
+
+/// 그룹에 포함된 대상의 상태로 장애 여부를 판단합니다.
+/// 상위 대상의 장애로 unreachable인 대상은 down으로 세지 않으므로, 그룹 안에 down 대상이 없으면 장애가 아닙니다.
+pub fn evaluate<'a>(config: &'a Config, group: &RecoveryGroup, health: &HealthTracker) -> Option<Outage<'a>> {
+    let selected: Vec<&NetworkTarget> = config.targets.iter().filter(|t| group.includes(t)).collect();
+    let root_causes: Vec<&NetworkTarget> = selected
+        .iter()
+        .copied()
+        .filter(|t| health.state(&t.name) == HealthState::Down)
+        .collect();
+    if root_causes.is_empty() {
+        return None;
+    }
+
+    let reason = match &group.policy {
+        OutagePolicy::All => {
+            let all_failed = selected
+                .iter()
+                .all(|t| matches!(health.state(&t.name), HealthState::Down | HealthState::Unreachable));
+            if !all_failed {
+                return None;
+            }
+            format!("대상 {}개 모두 연결 실패", selected.len())
+        }
+        OutagePolicy::Quorum { min_down } => {
+            let min_down = (*min_down).max(1);
+            if root_causes.len() < min_down {
+                return None;
+            }
+            format!("대상 {}개 중 {}개 down (기준 {}개)", selected.len(), root_causes.len(), min_down)
+        }
+        OutagePolicy::Any => format!("대상 {}개 중 {}개 down", selected.len(), root_causes.len()),
+        OutagePolicy::Weighted { min_score } => {
+            let score: f64 = root_causes.iter().map(|t| config.get_target_weight(t)).sum();
+            if score < *min_score {
+                return None;
+            }
+            format!("down 대상 가중치 합 {:.1} (기준 {:.1})", score, min_score)
+        }
+    };
+
+    Some(Outage { reason, root_causes })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::test_support;
+
+    fn target(name: &str, weight: Option<f64>, depends_on: &[&str]) -> NetworkTarget {
+        NetworkTarget { weight, ..test_support::target(name, depends_on) }
+    }
+
+    fn group(policy: OutagePolicy) -> RecoveryGroup {
+        RecoveryGroup {
+            name: "test".to_string(),
+            policy,
+            targets: Vec::new(),
+            tags: Vec::new(),
+            actions: Vec::new(),
+        }
+    }
+
+    /// 실패 한 번에 Down이 되는 설정
+    fn config(targets: Vec<NetworkTarget>) -> Config {
+        test_support::config(targets, 1)
+    }
+
+    fn record(health: &mut HealthTracker, config: &Config, name: &str, success: bool) {
+        let target = config.targets.iter().find(|t| t.name == name).unwrap();
+        health.record(config, target, success);
+    }
+
+    fn down_names(outage: Option<Outage<'_>>) -> Option<Vec<String>> {
+        outage.map(|o| o.root_causes.iter().map(|t| t.name.clone()).collect())
+    }
+
+    #[test]
+    fn all_requires_every_target_down_or_unreachable() {
+        let config = config(vec![target("a", None, &[]), target("b", None, &[]), target("c", None, &["a"])]);
+        let mut health = HealthTracker::default();
+        let policy = group(OutagePolicy::All);
+
+        record(&mut health, &config, "a", false);
+        record(&mut health, &config, "b", true);
+        assert!(evaluate(&config, &policy, &health).is_none());
+
+        record(&mut health, &config, "b", false);
+        // c는 a의 장애로 unreachable이므로 근본 원인에 포함되지 않음
+        record(&mut health, &config, "c", false);
+        assert_eq!(health.state("c"), HealthState::Unreachable);
+        assert_eq!(down_names(evaluate(&config, &policy, &health)), Some(vec!["a".to_string(), "b".to_string()]));
+    }
+
+    #[test]
+    fn unreachable_only_group_is_not_an_outage() {
+        let config = config(vec![target("parent", None, &[]), target("child", None, &["parent"])]);
+        let mut health = HealthTracker::default();
+        record(&mut health, &config, "parent", false);
+        record(&mut health, &config, "child", false);
+
+        let mut policy = group(OutagePolicy::All);
+        policy.targets = vec!["child".to_string()];
+        assert!(evaluate(&config, &policy, &health).is_none());
+    }
+
+    #[test]
+    fn quorum_counts_down_targets() {
+        let config = config(vec![target("a", None, &[]), target("b", None, &[]), target("c", None, &[])]);
+        let mut health = HealthTracker::default();
+        let policy = group(OutagePolicy::Quorum { min_down: 2 });
+
+        record(&mut health, &config, "a", false);
+        assert!(evaluate(&config, &policy, &health).is_none());
+        record(&mut health, &config, "b", false);
+        assert!(evaluate(&config, &policy, &health).is_some());
+
+        // min_down = 0은 1로 취급
+        let mut health = HealthTracker::default();
+        let policy = group(OutagePolicy::Quorum { min_down: 0 });
+        assert!(evaluate(&config, &policy, &health).is_none());
+        record(&mut health, &config, "c", false);
+        assert_eq!(down_names(evaluate(&config, &policy, &health)), Some(vec!["c".to_string()]));
+    }
+
+    #[test]
+    fn any_fires_on_first_down_target() {
+        let config = config(vec![target("a", None, &[]), target("b", None, &[])]);
+        let mut health = HealthTracker::default();
+        let policy = group(OutagePolicy::Any);
+        record(&mut health, &config, "a", true);
+        assert!(evaluate(&config, &policy, &health).is_none());
+        record(&mut health, &config, "b", false);
+        assert!(evaluate(&config, &policy, &health).is_some());
+    }
+
+    #[test]
+    fn weighted_sums_weights_of_down_targets() {
+        let config = config(vec![
+            target("heavy", Some(2.0), &[]),
+            target("light", None, &[]),
+            target("ignored", Some(0.0), &[]),
+        ]);
+        let policy = group(OutagePolicy::Weighted { min_score: 2.0 });
+
+        let mut health = HealthTracker::default();
+        record(&mut health, &config, "light", false);
+        assert!(evaluate(&config, &policy, &health).is_none());
+        record(&mut health, &config, "ignored", false);
+        assert!(evaluate(&config, &policy, &health).is_none());
+
+        // 합계가 기준과 같으면 장애
+        let mut health = HealthTracker::default();
+        record(&mut health, &config, "heavy", false);
+        assert!(evaluate(&config, &policy, &health).is_some());
+    }
+
+    #[test]
+    fn weighted_zero_score_still_needs_a_down_target() {
+        let config = config(vec![target("a", Some(0.0), &[])]);
+        let policy = group(OutagePolicy::Weighted { min_score: 0.0 });
+        let mut health = HealthTracker::default();
+        assert!(evaluate(&config, &policy, &health).is_none());
+        record(&mut health, &config, "a", false);
+        assert!(evaluate(&config, &policy, &health).is_some());
+    }
+
+    #[test]
+    fn group_only_counts_selected_targets() {
+        let mut internet = target("internet", None, &[]);
+        internet.tags = vec!["wan".to_string()];
+        let config = config(vec![internet, target("lan", None, &[])]);
+        let mut policy = group(OutagePolicy::All);
+        policy.tags = vec!["wan".to_string()];
+
+        let mut health = HealthTracker::default();
+        record(&mut health, &config, "lan", true);
+        record(&mut health, &config, "internet", false);
+        assert_eq!(down_names(evaluate(&config, &policy, &health)), Some(vec!["internet".to_string()]));
+    }
+}
@@ -0,0 +1,172 @@
+use crate::config::{Config, NetworkTarget};
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+/// 대상별 확인 주기에 맞춰 다음 확인 시각을 관리합니다.
+///
+/// 다음 확인은 이전 확인이 끝난 시점부터 `check_interval_sec` 뒤에 예약되며,
+/// 지터가 설정되어 있으면 0 ~ 지터 사이의 임의 시간만큼 늦춰집니다.
+/// 확인 중인 대상은 끝날 때까지 다시 예약되지 않습니다.
+#[derive(Debug, Default)]
+pub struct Scheduler {
+    next_due: HashMap<String, Instant>,
+    in_flight: HashSet<String>,
+}
+
+/// 0 ~ `jitter` 사이의 임의 지연 시간
+fn random_delay(jitter: Duration) -> Duration {
+    if jitter.is_zero() {
+        return Duration::ZERO;
+    }
+    let millis = rand::thread_rng().gen_range(0..=jitter.as_millis() as u64);
+    Duration::from_millis(millis)
+}
+
+impl Scheduler {
+    /// 설정의 대상 목록과 맞춥니다. 새 대상은 지터 범위 안에서 흩어서 예약하고,
+    /// 없어진 대상은 제거합니다.
+    pub fn sync(&mut self, config: &Config, now: Instant) {
+        self.next_due.retain(|name, _| config.targets.iter().any(|t| t.name == *name));
+        for target in &config.targets {
+            if !self.next_due.contains_key(&target.name) && !self.in_flight.contains(&target.name) {
+                let delay = random_delay(config.get_target_jitter(target));
+                self.next_due.insert(target.name.clone(), now + delay);
+            }
+        }
+    }
+
+    /// 확인할 때가 된 대상을 꺼내 확인 중으로 표시합니다.
+    pub fn take_due<'a>(&mut self, config: &'a Config, now: Instant) -> Vec<&'a NetworkTarget> {
+        let due: Vec<&NetworkTarget> = config
+            .targets
+            .iter()
+            .filter(|t| self.next_due.get(&t.name).is_some_and(|due| *due <= now))
+            .collect();
+        for target in &due {
+            self.next_due.remove(&target.name);
+            self.in_flight.insert(target.name.clone());
+        }
+        due
+    }
+
+    /// 확인이 끝난 대상의 다음 확인을 예약합니다.
+    pub fn complete(&mut self, config: &Config, name: &str, now: Instant) {
+        self.in_flight.remove(name);
+        if let Some(target) = config.targets.iter().find(|t| t.name == name) {
+            let delay = config.get_target_check_interval(target) + random_delay(config.get_target_jitter(target));
+            self.next_due.insert(name.to_string(), now + delay);
+        }
+    }
+
+    /// 인터페이스 이벤트 등으로 모든 대상을 즉시 확인하도록 예약합니다.
+    pub fn trigger_all(&mut self, now: Instant) {
+        for due in self.next_due.values_mut() {
+            *due = now;
+        }
+    }
+
+    /// 가장 가까운 다음 확인 시각
+    pub fn next_wakeup(&self) -> Option<Instant> {
+        self.next_due.values().min().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::test_support::{config, target};
+
+    /// 확인 주기 10초, 지터 50% (최대 5초)인 설정
+    fn jittered(names: &[&str]) -> Config {
+        Config {
+            check_interval_sec: 10,
+            jitter_percent: Some(50),
+            ..config(names.iter().map(|n| target(n, &[])).collect(), 3)
+        }
+    }
+
+    fn names(due: Vec<&NetworkTarget>) -> Vec<String> {
+        let mut names: Vec<String> = due.into_iter().map(|t| t.name.clone()).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn sync_spreads_new_targets_within_jitter() {
+        let config = jittered(&["a", "b"]);
+        let mut scheduler = Scheduler::default();
+        let now = Instant::now();
+        scheduler.sync(&config, now);
+
+        assert!(scheduler.take_due(&config, now - Duration::from_millis(1)).is_empty());
+        assert_eq!(names(scheduler.take_due(&config, now + Duration::from_secs(5))), ["a", "b"]);
+    }
+
+    #[test]
+    fn in_flight_target_is_not_rescheduled_by_sync() {
+        let config = jittered(&["a"]);
+        let mut scheduler = Scheduler::default();
+        let now = Instant::now();
+        scheduler.sync(&config, now);
+        assert_eq!(names(scheduler.take_due(&config, now + Duration::from_secs(5))), ["a"]);
+
+        scheduler.sync(&config, now);
+        assert_eq!(scheduler.next_wakeup(), None);
+        assert!(scheduler.take_due(&config, now + Duration::from_secs(60)).is_empty());
+    }
+
+    #[test]
+    fn complete_schedules_next_check_after_interval_plus_jitter() {
+        let config = jittered(&["a"]);
+        let mut scheduler = Scheduler::default();
+        let now = Instant::now();
+        scheduler.sync(&config, now);
+        scheduler.take_due(&config, now + Duration::from_secs(5));
+
+        let finished = now + Duration::from_secs(6);
+        scheduler.complete(&config, "a", finished);
+        let next = scheduler.next_wakeup().unwrap();
+        assert!(next >= finished + Duration::from_secs(10));
+        assert!(next <= finished + Duration::from_secs(15));
+    }
+
+    #[test]
+    fn trigger_all_makes_every_target_due_now() {
+        let config = jittered(&["a", "b"]);
+        let mut scheduler = Scheduler::default();
+        let now = Instant::now();
+        scheduler.sync(&config, now + Duration::from_secs(30));
+
+        scheduler.trigger_all(now);
+        assert_eq!(scheduler.next_wakeup(), Some(now));
+        assert_eq!(names(scheduler.take_due(&config, now)), ["a", "b"]);
+    }
+
+    #[test]
+    fn targets_removed_from_config_are_dropped() {
+        let mut scheduler = Scheduler::default();
+        let now = Instant::now();
+        scheduler.sync(&config(vec![target("a", &[]), target("b", &[])], 3), now);
+
+        let config = config(vec![target("a", &[])], 3);
+        scheduler.sync(&config, now);
+        assert_eq!(names(scheduler.take_due(&config, now)), ["a"]);
+        assert_eq!(scheduler.next_wakeup(), None);
+    }
+
+    #[test]
+    fn next_wakeup_returns_earliest_due_time() {
+        let config = config(vec![target("a", &[]), target("b", &[])], 3);
+        let mut scheduler = Scheduler::default();
+        let now = Instant::now();
+        assert_eq!(scheduler.next_wakeup(), None);
+
+        scheduler.sync(&config, now);
+        scheduler.take_due(&config, now);
+        scheduler.complete(&config, "a", now + Duration::from_secs(5));
+        scheduler.complete(&config, "b", now + Duration::from_secs(2));
+        let interval = config.get_target_check_interval(&config.targets[1]);
+        assert_eq!(scheduler.next_wakeup(), Some(now + Duration::from_secs(2) + interval));
+    }
+}
@@ -0,0 +1,192 @@
+use crate::config::{Config, NetworkTarget, RecoveryAction, RecoveryVerify};
+use crate::network;
+use crate::probe;
+use futures_util::future::join_all;
+use std::fmt;
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tokio::time;
+
+/// 복구 작업 후 확인 결과입니다.
+#[derive(Debug, Clone)]
+pub struct Verification {
+    /// 기준 이상의 대상이 확인에 성공했는지 여부
+    pub success: bool,
+    /// 마지막 시도에서 성공한 대상 수
+    pub passed: usize,
+    /// 확인한 대상 수
+    pub total: usize,
+    /// 성공해야 하는 대상 수
+    pub required: usize,
+    /// 시도 횟수
+    pub attempts: u32,
+    pub elapsed: Duration,
+    /// 마지막 시도에서 실패한 대상 이름
+    pub failed: Vec<String>,
+}
+
+impl fmt::Display for Verification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "대상 {}개 중 {}개 성공 (기준 {}개, 시도 {}회, {:.1}초)",
+            self.total,
+            self.passed,
+            self.required,
+            self.attempts,
+            self.elapsed.as_secs_f64()
+        )?;
+        if !self.failed.is_empty() {
+            write!(f, ", 실패: {}", self.failed.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// 복구 작업의 verify 설정으로 확인할 대상 목록을 만듭니다.
+fn verify_targets<'a>(config: &'a Config, action: &'a RecoveryAction) -> Vec<&'a NetworkTarget> {
+    let Some(verify) = &action.verify else {
+        return Vec::new();
+    };
+    verify
+        .targets
+        .iter()
+        .filter_map(|name| config.targets.iter().find(|t| t.name == *name))
+        .chain(&verify.checks)
+        .collect()
+}
+
+/// 확인 대상을 동시에 한 번씩 확인하고 실패한 대상 이름을 반환합니다.
+/// 확인 대상이 없으면 default_target으로 핑을 보냅니다.
+async fn check_once(config: &Config, targets: &[&NetworkTarget]) -> Vec<String> {
+    if targets.is_empty() {
+        let timeout = Duration::from_millis(config.ping_timeout_ms);
+        return match network::ping_host(&config.default_target, timeout).await {
+            Ok(_) => Vec::new(),
+            Err(_) => vec![config.default_target.clone()],
+        };
+    }
+
+    // 응답이 느린 대상이 확인 기간을 다 쓰지 않도록 동시에 확인
+    let reports = join_all(targets.iter().map(|target| probe::run_probe(config, target))).await;
+    targets
+        .iter()
+        .zip(reports)
+        .filter(|(_, report)| !report.is_success())
+        .map(|(target, _)| target.name.clone())
+        .collect()
+}
+
+/// 복구 작업 후 연결이 복구되었는지 확인합니다.
+/// 확인 대상을 모두 확인하고, 성공한 대상이 기준에 못 미치면 확인 기간(window) 안에서 재시도합니다.
+pub async fn verify_recovery(config: &Config, action: &RecoveryAction) -> Verification {
+    let targets = verify_targets(config, action);
+    let verify = action.verify.clone().unwrap_or_default();
+    verify_with(&verify, targets.len().max(1), || check_once(config, &targets)).await
+}
+
+/// `check`로 `total`개 대상을 확인하며 `verify`의 기준과 확인 기간에 따라 재시도합니다.
+/// `check`는 실패한 대상 이름을 반환합니다.
+async fn verify_with<F, Fut>(verify: &RecoveryVerify, total: usize, mut check: F) -> Verification
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Vec<String>>,
+{
+    let required = verify.get_min_success(total);
+    let started = Instant::now();
+    let deadline = started + verify.get_window();
+
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let failed = check().await;
+        let passed = total - failed.len();
+        let success = passed >= required;
+
+        let retry_at = Instant::now() + verify.get_retry_interval();
+        if success || retry_at > deadline {
+            return Verification {
+                success,
+                passed,
+                total,
+                required,
+                attempts,
+                elapsed: started.elapsed(),
+                failed,
+            };
+        }
+        time::sleep_until(retry_at.into()).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verify(min_success: Option<usize>, window_sec: u64, retry_interval_ms: u64) -> RecoveryVerify {
+        RecoveryVerify {
+            min_success,
+            window_sec: Some(window_sec),
+            retry_interval_ms: Some(retry_interval_ms),
+            ..Default::default()
+        }
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn counts_quorum_of_passed_targets() {
+        let quorum = verify(Some(2), 0, 100);
+        let result = verify_with(&quorum, 3, || async { names(&["c"]) }).await;
+        assert!(result.success);
+        assert_eq!((result.passed, result.total, result.required), (2, 3, 2));
+        assert_eq!(result.failed, names(&["c"]));
+
+        let result = verify_with(&quorum, 3, || async { names(&["b", "c"]) }).await;
+        assert!(!result.success);
+        assert_eq!(result.passed, 1);
+
+        // 기본값은 전부 성공, 기준은 대상 수를 넘지 않음
+        assert!(!verify_with(&verify(None, 0, 100), 3, || async { names(&["c"]) }).await.success);
+        let result = verify_with(&verify(Some(5), 0, 100), 2, || async { Vec::new() }).await;
+        assert!(result.success);
+        assert_eq!(result.required, 2);
+    }
+
+    #[tokio::test]
+    async fn checks_once_without_window() {
+        let mut calls = 0;
+        let result = verify_with(&verify(None, 0, 100), 1, || {
+            calls += 1;
+            async { names(&["a"]) }
+        })
+        .await;
+        assert!(!result.success);
+        assert_eq!((result.attempts, calls), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn retries_until_success_within_window() {
+        let mut calls = 0;
+        let result = verify_with(&verify(None, 5, 100), 1, || {
+            calls += 1;
+            let failed = if calls < 3 { names(&["a"]) } else { Vec::new() };
+            async move { failed }
+        })
+        .await;
+        assert!(result.success);
+        assert_eq!(result.attempts, 3);
+        assert!(result.elapsed < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn stops_retrying_at_window_end() {
+        let result = verify_with(&verify(None, 1, 300), 1, || async { names(&["a"]) }).await;
+        assert!(!result.success);
+        // 0, 300, 600, 900ms 무렵에 시도하고, 확인 기간(1초)을 넘는 시도는 하지 않음
+        assert!((2..=4).contains(&result.attempts), "{}", result.attempts);
+        assert!(result.elapsed < Duration::from_secs(1));
+    }
+}
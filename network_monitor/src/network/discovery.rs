@@ -0,0 +1,210 @@
+use anyhow::Result;
+#[cfg(any(target_os = "linux", windows))]
+use anyhow::anyhow;
+use log::warn;
+use std::net::IpAddr;
+
+/// 시스템에서 찾은 기본 게이트웨이와 DNS 서버입니다.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiscoveredServices {
+    pub gateways: Vec<IpAddr>,
+    pub dns_servers: Vec<IpAddr>,
+}
+
+/// 기본 게이트웨이와 DNS 서버를 찾습니다. 한쪽을 찾지 못해도 다른 쪽 결과는 반환합니다.
+pub fn discover() -> DiscoveredServices {
+    let gateways = default_gateways().unwrap_or_else(|e| {
+        warn!("기본 게이트웨이 검색 실패: {}", e);
+        Vec::new()
+    });
+    let dns_servers = super::system_dns_servers().unwrap_or_else(|e| {
+        warn!("DNS 서버 검색 실패: {}", e);
+        Vec::new()
+    });
+
+    DiscoveredServices {
+        gateways: dedup(gateways),
+        dns_servers: dedup(dns_servers),
+    }
+}
+
+fn dedup(addrs: Vec<IpAddr>) -> Vec<IpAddr> {
+    let mut unique = Vec::new();
+    for addr in addrs {
+        if !unique.contains(&addr) {
+            unique.push(addr);
+        }
+    }
+    unique
+}
+
+/// 기본 경로 한 개 (출력 인터페이스와 게이트웨이)
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DefaultRoute {
+    pub interface: String,
+    pub gateway: IpAddr,
+}
+
+/// 라우팅 테이블에서 기본 경로(0.0.0.0/0, ::/0) 목록을 가져옵니다.
+#[cfg(target_os = "linux")]
+pub fn default_routes() -> Result<Vec<DefaultRoute>> {
+    let route = std::fs::read_to_string("/proc/net/route")
+        .map_err(|e| anyhow!("/proc/net/route 읽기 실패: {}", e))?;
+    let mut routes = parse_proc_route(&route);
+
+    // IPv6가 비활성화된 시스템에는 파일이 없을 수 있음
+    if let Ok(route6) = std::fs::read_to_string("/proc/net/ipv6_route") {
+        routes.extend(parse_proc_ipv6_route(&route6));
+    }
+    Ok(routes)
+}
+
+/// 라우팅 테이블에서 기본 경로(0.0.0.0/0, ::/0)의 게이트웨이를 가져옵니다.
+#[cfg(target_os = "linux")]
+pub fn default_gateways() -> Result<Vec<IpAddr>> {
+    Ok(default_routes()?
+        .into_iter()
+        .map(|route| route.gateway)
+        .filter(|gateway| !is_link_local(gateway))
+        .collect())
+}
+
+/// 라우팅 테이블에서 기본 경로(0.0.0.0/0, ::/0)의 게이트웨이를 가져옵니다.
+#[cfg(windows)]
+pub fn default_gateways() -> Result<Vec<IpAddr>> {
+    use std::str::FromStr;
+
+    let output = std::process::Command::new("powershell")
+        .args([
+            "-Command",
+            "Get-NetRoute -DestinationPrefix '0.0.0.0/0','::/0' | Select-Object -ExpandProperty NextHop",
+        ])
+        .output()
+        .map_err(|e| anyhow!("라우팅 정보 가져오기 실패: {}", e))?;
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| IpAddr::from_str(line.trim()).ok())
+        .filter(|addr| !addr.is_unspecified() && !is_link_local(addr))
+        .collect())
+}
+
+/// 라우팅 테이블에서 기본 경로(0.0.0.0/0, ::/0)의 게이트웨이를 가져옵니다.
+#[cfg(not(any(target_os = "linux", windows)))]
+pub fn default_gateways() -> Result<Vec<IpAddr>> {
+    Ok(Vec::new())
+}
+
+/// 링크 로컬 IPv6 게이트웨이는 인터페이스 지정 없이 핑할 수 없으므로 제외합니다.
+#[cfg(any(target_os = "linux", windows))]
+fn is_link_local(addr: &IpAddr) -> bool {
+    match addr {
+        IpAddr::V4(_) => false,
+        IpAddr::V6(v6) => (v6.segments()[0] & 0xffc0) == 0xfe80,
+    }
+}
+
+/// `/proc/net/route`에서 IPv4 기본 경로를 추출합니다.
+/// 주소는 리틀 엔디언 16진수로 기록되어 있습니다.
+#[cfg(target_os = "linux")]
+pub fn parse_proc_route(content: &str) -> Vec<DefaultRoute> {
+    const RTF_UP: u16 = 0x0001;
+    const RTF_GATEWAY: u16 = 0x0002;
+
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            // Iface Destination Gateway Flags RefCnt Use Metric Mask ...
+            let (interface, destination, gateway, flags, mask) =
+                (fields.first()?, fields.get(1)?, fields.get(2)?, fields.get(3)?, fields.get(7)?);
+            let flags = u16::from_str_radix(flags, 16).ok()?;
+            if *destination != "00000000" || *mask != "00000000" || flags & (RTF_UP | RTF_GATEWAY) != RTF_UP | RTF_GATEWAY {
+                return None;
+            }
+            let gateway = IpAddr::from(u32::from_str_radix(gateway, 16).ok()?.to_le_bytes());
+            Some(DefaultRoute {
+                interface: interface.to_string(),
+                gateway,
+            })
+        })
+        .filter(|route| !route.gateway.is_unspecified())
+        .collect()
+}
+
+/// `/proc/net/ipv6_route`에서 IPv6 기본 경로를 추출합니다.
+#[cfg(target_os = "linux")]
+pub fn parse_proc_ipv6_route(content: &str) -> Vec<DefaultRoute> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            // dest dest_len src src_len next_hop metric refcnt use flags iface
+            let (destination, prefix_len, next_hop, interface) =
+                (fields.first()?, fields.get(1)?, fields.get(4)?, fields.get(9)?);
+            if destination.chars().any(|c| c != '0') || *prefix_len != "00" {
+                return None;
+            }
+            let gateway = IpAddr::from(u128::from_str_radix(next_hop, 16).ok()?.to_be_bytes());
+            Some(DefaultRoute {
+                interface: interface.to_string(),
+                gateway,
+            })
+        })
+        .filter(|route| !route.gateway.is_unspecified())
+        .collect()
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    const PROC_ROUTE: &str = "\
+Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT
+eth0\t00000000\t0101A8C0\t0003\t0\t0\t100\t00000000\t0\t0\t0
+eth0\t0001A8C0\t00000000\t0001\t0\t0\t100\t00FFFFFF\t0\t0\t0
+wlan0\t00000000\t010010AC\t0001\t0\t0\t600\t00000000\t0\t0\t0
+docker0\t000011AC\t00000000\t0001\t0\t0\t0\t0000FFFF\t0\t0\t0
+";
+
+    #[test]
+    fn parses_ipv4_default_route_with_gateway_flag() {
+        let routes = parse_proc_route(PROC_ROUTE);
+        assert_eq!(
+            routes,
+            vec![DefaultRoute {
+                interface: "eth0".to_string(),
+                gateway: "192.168.1.1".parse().unwrap(),
+            }]
+        );
+    }
+
+    #[test]
+    fn ignores_malformed_and_unspecified_routes() {
+        let content = "Iface\tDestination\tGateway\nbroken\t00000000\n\
+                       eth1\t00000000\t00000000\t0003\t0\t0\t0\t00000000\t0\t0\t0\n\
+                       eth2\t00000000\tZZZZZZZZ\t0003\t0\t0\t0\t00000000\t0\t0\t0\n";
+        assert!(parse_proc_route(content).is_empty());
+        assert!(parse_proc_route("").is_empty());
+    }
+
+    #[test]
+    fn parses_ipv6_default_route() {
+        let content = "\
+00000000000000000000000000000000 00 00000000000000000000000000000000 00 fe800000000000000000000000000001 00000400 00000001 00000000 00450003 eth0
+20010db8000000000000000000000000 40 00000000000000000000000000000000 00 00000000000000000000000000000000 00000100 00000001 00000000 00000001 eth0
+00000000000000000000000000000000 00 00000000000000000000000000000000 00 00000000000000000000000000000000 ffffffff 00000001 00000000 00200200 lo
+";
+        let routes = parse_proc_ipv6_route(content);
+        assert_eq!(
+            routes,
+            vec![DefaultRoute {
+                interface: "eth0".to_string(),
+                gateway: "fe80::1".parse().unwrap(),
+            }]
+        );
+        assert!(is_link_local(&routes[0].gateway));
+    }
+}
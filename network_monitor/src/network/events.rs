@@ -0,0 +1,410 @@
+use super::interfaces::InterfaceAddress;
+use anyhow::Result;
+use std::fmt;
+use std::time::Duration;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+
+/// 인터페이스 상태 변화의 종류입니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkEventKind {
+    LinkUp,
+    LinkDown,
+    AddressAdded(InterfaceAddress),
+    AddressRemoved(InterfaceAddress),
+}
+
+/// 인터페이스 링크/주소 변경 이벤트입니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkEvent {
+    pub interface: String,
+    pub kind: LinkEventKind,
+}
+
+impl fmt::Display for LinkEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            LinkEventKind::LinkUp => write!(f, "{} 링크 연결됨", self.interface),
+            LinkEventKind::LinkDown => write!(f, "{} 링크 끊김", self.interface),
+            LinkEventKind::AddressAdded(address) => write!(f, "{} 주소 추가 {}", self.interface, address),
+            LinkEventKind::AddressRemoved(address) => write!(f, "{} 주소 제거 {}", self.interface, address),
+        }
+    }
+}
+
+/// 수신 측이 닫혔는지 확인하는 간격 (이벤트가 없어도 이 간격 안에 스레드가 종료됨)
+const STOP_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 인터페이스 이벤트 구독을 시작합니다. 이벤트는 백그라운드 스레드에서 수신되어 채널로 전달됩니다.
+/// 리눅스는 netlink(RTMGRP_LINK, RTMGRP_IPV4_IFADDR, RTMGRP_IPV6_IFADDR)를 사용하고,
+/// 그 외 플랫폼은 인터페이스 목록을 30초마다 비교합니다.
+pub fn subscribe() -> Result<UnboundedReceiver<LinkEvent>> {
+    let (tx, rx) = unbounded_channel();
+    let mut source = EventSource::open()?;
+
+    std::thread::Builder::new()
+        .name("link-events".to_string())
+        .spawn(move || {
+            // 수신 측이 닫히면 이벤트를 기다리는 중에도 STOP_CHECK_INTERVAL 안에 스레드 종료
+            while let Ok(Some(events)) = source.next_events(&|| tx.is_closed()) {
+                for event in events {
+                    if tx.send(event).is_err() {
+                        return;
+                    }
+                }
+            }
+        })?;
+
+    Ok(rx)
+}
+
+#[cfg(target_os = "linux")]
+use netlink::EventSource;
+#[cfg(not(target_os = "linux"))]
+use polling::EventSource;
+
+#[cfg(target_os = "linux")]
+mod netlink {
+    use super::{LinkEvent, LinkEventKind, STOP_CHECK_INTERVAL};
+    use crate::network::interfaces::InterfaceAddress;
+    use anyhow::{anyhow, Result};
+    use std::collections::HashMap;
+    use std::io;
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+    const NLMSG_HDR_LEN: usize = 16;
+    const IFINFOMSG_LEN: usize = 16;
+    const IFADDRMSG_LEN: usize = 8;
+    const IFLA_IFNAME: u16 = 3;
+    const IFA_ADDRESS: u16 = 1;
+    const IFA_LOCAL: u16 = 2;
+
+    /// netlink 라우팅 소켓으로 링크/주소 변경을 수신합니다.
+    pub struct EventSource {
+        socket: OwnedFd,
+        /// 인터페이스 인덱스별 마지막 링크 상태 (같은 상태의 반복 알림 제거용)
+        link_up: HashMap<i32, bool>,
+        buf: Vec<u8>,
+    }
+
+    fn align(len: usize) -> usize {
+        (len + 3) & !3
+    }
+
+    fn u16_at(buf: &[u8], offset: usize) -> Option<u16> {
+        Some(u16::from_ne_bytes(buf.get(offset..offset + 2)?.try_into().ok()?))
+    }
+
+    fn u32_at(buf: &[u8], offset: usize) -> Option<u32> {
+        Some(u32::from_ne_bytes(buf.get(offset..offset + 4)?.try_into().ok()?))
+    }
+
+    /// rtattr 목록을 (타입, 데이터)로 나눕니다.
+    fn attributes(mut buf: &[u8]) -> Vec<(u16, &[u8])> {
+        let mut attrs = Vec::new();
+        while buf.len() >= 4 {
+            let (Some(len), Some(kind)) = (u16_at(buf, 0), u16_at(buf, 2)) else {
+                break;
+            };
+            let len = usize::from(len);
+            if len < 4 || len > buf.len() {
+                break;
+            }
+            attrs.push((kind, &buf[4..len]));
+            buf = &buf[align(len).min(buf.len())..];
+        }
+        attrs
+    }
+
+    fn interface_name(index: u32) -> String {
+        let mut name = [0 as libc::c_char; libc::IF_NAMESIZE];
+        // SAFETY: 버퍼 크기가 IF_NAMESIZE 이상이며, 성공 시 NUL 종료 문자열이 기록됨
+        let result = unsafe { libc::if_indextoname(index, name.as_mut_ptr()) };
+        if result.is_null() {
+            return format!("#{}", index);
+        }
+        // SAFETY: if_indextoname이 성공했으므로 NUL 종료 문자열
+        unsafe { std::ffi::CStr::from_ptr(name.as_ptr()) }
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// 구독 시작 시점의 링크 상태를 sysfs에서 읽습니다 (IFF_UP과 carrier = IFF_LOWER_UP).
+    fn current_link_states() -> HashMap<i32, bool> {
+        let mut states = HashMap::new();
+        let Ok(entries) = std::fs::read_dir("/sys/class/net") else {
+            return states;
+        };
+        for entry in entries.flatten() {
+            let read = |name: &str| std::fs::read_to_string(entry.path().join(name)).ok();
+            let Some(index) = read("ifindex").and_then(|v| v.trim().parse().ok()) else {
+                continue;
+            };
+            let admin_up = read("flags")
+                .and_then(|v| u32::from_str_radix(v.trim().trim_start_matches("0x"), 16).ok())
+                .is_some_and(|flags| flags & libc::IFF_UP as u32 != 0);
+            // 인터페이스가 내려가 있으면 carrier 읽기가 실패함
+            let carrier = read("carrier").is_some_and(|v| v.trim() == "1");
+            states.insert(index, admin_up && carrier);
+        }
+        states
+    }
+
+    impl EventSource {
+        pub fn open() -> Result<Self> {
+            // SAFETY: 인자가 유효한 상수인 socket 호출
+            let fd = unsafe {
+                libc::socket(libc::AF_NETLINK, libc::SOCK_RAW | libc::SOCK_CLOEXEC, libc::NETLINK_ROUTE)
+            };
+            if fd < 0 {
+                return Err(anyhow!("netlink 소켓 생성 실패: {}", io::Error::last_os_error()));
+            }
+            // SAFETY: 방금 생성한 유효한 파일 디스크립터의 소유권을 넘김
+            let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+            // SAFETY: sockaddr_nl은 0으로 초기화해도 유효한 C 구조체
+            let mut addr: libc::sockaddr_nl = unsafe { std::mem::zeroed() };
+            addr.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+            addr.nl_groups = (libc::RTMGRP_LINK | libc::RTMGRP_IPV4_IFADDR | libc::RTMGRP_IPV6_IFADDR) as u32;
+            // SAFETY: addr은 올바른 크기의 sockaddr_nl
+            let result = unsafe {
+                libc::bind(
+                    socket.as_raw_fd(),
+                    &addr as *const libc::sockaddr_nl as *const libc::sockaddr,
+                    std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+                )
+            };
+            if result < 0 {
+                return Err(anyhow!("netlink 소켓 바인드 실패: {}", io::Error::last_os_error()));
+            }
+
+            // 수신 측이 닫혔는지 주기적으로 확인할 수 있도록 수신 대기 시간을 제한
+            let timeout = libc::timeval {
+                tv_sec: STOP_CHECK_INTERVAL.as_secs() as libc::time_t,
+                tv_usec: 0,
+            };
+            // SAFETY: timeout은 올바른 크기의 timeval
+            let result = unsafe {
+                libc::setsockopt(
+                    socket.as_raw_fd(),
+                    libc::SOL_SOCKET,
+                    libc::SO_RCVTIMEO,
+                    &timeout as *const libc::timeval as *const libc::c_void,
+                    std::mem::size_of::<libc::timeval>() as libc::socklen_t,
+                )
+            };
+            if result < 0 {
+                return Err(anyhow!("netlink 소켓 수신 시간 제한 설정 실패: {}", io::Error::last_os_error()));
+            }
+
+            Ok(Self {
+                socket,
+                link_up: current_link_states(),
+                buf: vec![0u8; 16 * 1024],
+            })
+        }
+
+        /// 다음 netlink 메시지 묶음을 기다렸다가 이벤트로 변환합니다.
+        /// 기다리는 동안 `is_closed`가 true를 반환하면 `None`을 반환합니다.
+        pub fn next_events(&mut self, is_closed: &dyn Fn() -> bool) -> Result<Option<Vec<LinkEvent>>> {
+            let len = loop {
+                // SAFETY: buf는 len 바이트 크기의 쓰기 가능한 버퍼
+                let len = unsafe {
+                    libc::recv(
+                        self.socket.as_raw_fd(),
+                        self.buf.as_mut_ptr() as *mut libc::c_void,
+                        self.buf.len(),
+                        0,
+                    )
+                };
+                if len >= 0 {
+                    break len as usize;
+                }
+                let error = io::Error::last_os_error();
+                match error.kind() {
+                    io::ErrorKind::Interrupted => continue,
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => {
+                        if is_closed() {
+                            return Ok(None);
+                        }
+                        continue;
+                    }
+                    // 수신 버퍼가 넘쳐 일부 알림이 유실된 경우에도 계속 수신
+                    _ if error.raw_os_error() == Some(libc::ENOBUFS) => continue,
+                    _ => return Err(anyhow!("netlink 수신 실패: {}", error)),
+                }
+            };
+
+            let buf = self.buf[..len].to_vec();
+            let mut events = Vec::new();
+            let mut offset = 0;
+            while offset + NLMSG_HDR_LEN <= buf.len() {
+                let (Some(msg_len), Some(msg_type)) = (u32_at(&buf, offset), u16_at(&buf, offset + 4)) else {
+                    break;
+                };
+                let msg_len = msg_len as usize;
+                if msg_len < NLMSG_HDR_LEN || offset + msg_len > buf.len() {
+                    break;
+                }
+                let payload = &buf[offset + NLMSG_HDR_LEN..offset + msg_len];
+                match msg_type {
+                    libc::RTM_NEWLINK | libc::RTM_DELLINK => {
+                        events.extend(self.parse_link(payload, msg_type == libc::RTM_DELLINK));
+                    }
+                    libc::RTM_NEWADDR | libc::RTM_DELADDR => {
+                        events.extend(parse_address(payload, msg_type == libc::RTM_DELADDR));
+                    }
+                    _ => {}
+                }
+                offset += align(msg_len);
+            }
+            Ok(Some(events))
+        }
+
+        fn parse_link(&mut self, payload: &[u8], deleted: bool) -> Option<LinkEvent> {
+            if payload.len() < IFINFOMSG_LEN {
+                return None;
+            }
+            let index = u32_at(payload, 4)? as i32;
+            let flags = u32_at(payload, 8)?;
+            let up = !deleted && flags & libc::IFF_LOWER_UP as u32 != 0 && flags & libc::IFF_UP as u32 != 0;
+
+            // 상태가 바뀐 경우에만 이벤트 생성 (새 인터페이스는 내려간 상태였던 것으로 간주)
+            let previous = self.link_up.insert(index, up).unwrap_or(false);
+            if deleted {
+                self.link_up.remove(&index);
+            }
+            if previous == up {
+                return None;
+            }
+
+            let interface = attributes(&payload[IFINFOMSG_LEN..])
+                .into_iter()
+                .find(|(kind, _)| *kind == IFLA_IFNAME)
+                .map(|(_, data)| String::from_utf8_lossy(data).trim_end_matches('\0').to_string())
+                .unwrap_or_else(|| interface_name(index as u32));
+
+            Some(LinkEvent {
+                interface,
+                kind: if up { LinkEventKind::LinkUp } else { LinkEventKind::LinkDown },
+            })
+        }
+    }
+
+    fn parse_address(payload: &[u8], removed: bool) -> Option<LinkEvent> {
+        if payload.len() < IFADDRMSG_LEN {
+            return None;
+        }
+        let family = i32::from(payload[0]);
+        let prefix_len = payload[1];
+        let index = u32_at(payload, 4)?;
+
+        // 점대점 링크에서는 IFA_LOCAL이 로컬 주소이므로 우선 사용
+        let attrs = attributes(&payload[IFADDRMSG_LEN..]);
+        let data = attrs
+            .iter()
+            .find(|(kind, _)| *kind == IFA_LOCAL)
+            .or_else(|| attrs.iter().find(|(kind, _)| *kind == IFA_ADDRESS))
+            .map(|(_, data)| *data)?;
+        let address = match family {
+            libc::AF_INET => IpAddr::V4(Ipv4Addr::from(<[u8; 4]>::try_from(data.get(..4)?).ok()?)),
+            libc::AF_INET6 => IpAddr::V6(Ipv6Addr::from(<[u8; 16]>::try_from(data.get(..16)?).ok()?)),
+            _ => return None,
+        };
+
+        let address = InterfaceAddress { address, prefix_len };
+        Some(LinkEvent {
+            interface: interface_name(index),
+            kind: if removed {
+                LinkEventKind::AddressRemoved(address)
+            } else {
+                LinkEventKind::AddressAdded(address)
+            },
+        })
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod polling {
+    use super::{LinkEvent, LinkEventKind, STOP_CHECK_INTERVAL};
+    use crate::network::interfaces::{self, NetworkInterface};
+    use anyhow::Result;
+    use std::time::{Duration, Instant};
+
+    /// 인터페이스 목록 조회는 외부 프로세스를 실행하므로 간격을 길게 둠
+    const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+    /// 인터페이스 목록을 주기적으로 가져와 이전 목록과 비교합니다.
+    pub struct EventSource {
+        previous: Vec<NetworkInterface>,
+    }
+
+    impl EventSource {
+        pub fn open() -> Result<Self> {
+            Ok(Self {
+                previous: interfaces::list()?,
+            })
+        }
+
+        /// 목록이 바뀔 때까지 주기적으로 비교합니다.
+        /// 기다리는 동안 `is_closed`가 true를 반환하면 `None`을 반환합니다.
+        pub fn next_events(&mut self, is_closed: &dyn Fn() -> bool) -> Result<Option<Vec<LinkEvent>>> {
+            loop {
+                let next_poll = Instant::now() + POLL_INTERVAL;
+                while Instant::now() < next_poll {
+                    if is_closed() {
+                        return Ok(None);
+                    }
+                    std::thread::sleep(STOP_CHECK_INTERVAL.min(next_poll.saturating_duration_since(Instant::now())));
+                }
+                let current = match interfaces::list() {
+                    Ok(current) => current,
+                    Err(_) => continue,
+                };
+                let events = diff(&self.previous, &current);
+                self.previous = current;
+                if !events.is_empty() {
+                    return Ok(Some(events));
+                }
+            }
+        }
+    }
+
+    fn diff(previous: &[NetworkInterface], current: &[NetworkInterface]) -> Vec<LinkEvent> {
+        let mut events = Vec::new();
+        for interface in current {
+            let before = previous.iter().find(|p| p.index == interface.index);
+            let was_up = before.is_some_and(NetworkInterface::is_up);
+            if before.is_some() && was_up != interface.is_up() {
+                events.push(LinkEvent {
+                    interface: interface.name.clone(),
+                    kind: if interface.is_up() { LinkEventKind::LinkUp } else { LinkEventKind::LinkDown },
+                });
+            }
+
+            let old_addresses: Vec<_> = before.map(|b| b.ipv4.iter().chain(&b.ipv6).copied().collect()).unwrap_or_default();
+            let new_addresses: Vec<_> = interface.ipv4.iter().chain(&interface.ipv6).copied().collect();
+            for address in new_addresses.iter().filter(|a| !old_addresses.contains(a)) {
+                events.push(LinkEvent {
+                    interface: interface.name.clone(),
+                    kind: LinkEventKind::AddressAdded(*address),
+                });
+            }
+            for address in old_addresses.iter().filter(|a| !new_addresses.contains(a)) {
+                events.push(LinkEvent {
+                    interface: interface.name.clone(),
+                    kind: LinkEventKind::AddressRemoved(*address),
+                });
+            }
+        }
+        for interface in previous.iter().filter(|p| !current.iter().any(|c| c.index == p.index)) {
+            if interface.is_up() {
+                events.push(LinkEvent {
+                    interface: interface.name.clone(),
+                    kind: LinkEventKind::LinkDown,
+                });
+            }
+        }
+        events
+    }
+}
@@ -0,0 +1,227 @@
+use super::timing::{self, ConnectionTiming};
+use anyhow::{anyhow, Result};
+use hyper::body::{Body, Bytes, HttpBody};
+use hyper::header::{HeaderName, HeaderValue, HOST, LOCATION, USER_AGENT};
+use hyper::{Method, Request, StatusCode};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::time::timeout;
+use url::Url;
+
+/// 따라갈 최대 리디렉션 횟수
+const MAX_REDIRECTS: usize = 10;
+/// 본문 검사를 위해 읽을 최대 바이트 수 (이후 내용은 버림)
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+/// 다른 출처(origin)로 리디렉션할 때 보내지 않는 헤더 (인증 정보와 원래 호스트 지정)
+const CROSS_ORIGIN_STRIPPED_HEADERS: [&str; 6] =
+    ["authorization", "proxy-authorization", "cookie", "cookie2", "www-authenticate", "host"];
+
+/// HTTP 요청에 사용할 설정입니다.
+pub struct HttpRequest<'a> {
+    pub method: Method,
+    pub headers: &'a BTreeMap<String, String>,
+    pub verify_tls: bool,
+    /// 이 상태 코드들은 리디렉션이어도 따라가지 않고 그대로 반환
+    pub stop_on_status: &'a [u16],
+    /// 응답 본문을 읽을지 여부 (최대 `MAX_BODY_BYTES`까지만 읽음)
+    pub read_body: bool,
+}
+
+/// HTTP 응답과 마지막 요청의 단계별 시간입니다.
+pub struct HttpResponse {
+    pub url: Url,
+    pub status: StatusCode,
+    pub body: Option<Bytes>,
+    pub timing: ConnectionTiming,
+}
+
+trait Io: AsyncRead + AsyncWrite + Send + Unpin {}
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> Io for T {}
+
+/// 요청 한 번을 보내고 응답 헤더를 받을 때까지의 단계별 시간을 측정합니다.
+async fn send_once(
+    url: &Url,
+    request: &HttpRequest<'_>,
+    method: &Method,
+    headers: &BTreeMap<String, String>,
+    timeout_duration: Duration,
+) -> Result<(hyper::Response<Body>, ConnectionTiming)> {
+    let host = url.host_str().ok_or_else(|| anyhow!("URL에 호스트가 없음: {}", url))?;
+    let port = url
+        .port_or_known_default()
+        .ok_or_else(|| anyhow!("URL의 포트를 알 수 없음: {}", url))?;
+
+    let (io, mut timing): (Box<dyn Io>, ConnectionTiming) = match url.scheme() {
+        "http" => {
+            let (stream, timing) = timing::connect(host, port, timeout_duration).await?;
+            (Box::new(stream), timing)
+        }
+        "https" => {
+            let server_name = host.trim_start_matches('[').trim_end_matches(']');
+            let (stream, timing) =
+                timing::connect_tls(host, port, server_name, request.verify_tls, timeout_duration).await?;
+            (Box::new(stream), timing)
+        }
+        scheme => return Err(anyhow!("지원하지 않는 URL 스킴: {}", scheme)),
+    };
+
+    let (mut sender, connection) = hyper::client::conn::handshake(io)
+        .await
+        .map_err(|e| anyhow!("HTTP 연결 설정 실패: {}", e))?;
+    tokio::spawn(async move {
+        let _ = connection.await;
+    });
+
+    let http_request = build_request(url, method, headers)?;
+
+    let request_start = Instant::now();
+    let response = sender
+        .send_request(http_request)
+        .await
+        .map_err(|e| anyhow!("HTTP 요청 실패: {}", e))?;
+    timing.first_byte = Some(request_start.elapsed());
+
+    Ok((response, timing))
+}
+
+/// 요청을 만듭니다. 사용자가 지정한 헤더는 기본 `Host`/`User-Agent` 헤더를 대신합니다.
+fn build_request(url: &Url, method: &Method, headers: &BTreeMap<String, String>) -> Result<Request<Body>> {
+    let host = url.host_str().ok_or_else(|| anyhow!("URL에 호스트가 없음: {}", url))?;
+    let path = match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    };
+    let host_header = match url.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_string(),
+    };
+    let mut http_request = Request::builder()
+        .method(method.clone())
+        .uri(path)
+        .header(HOST, host_header)
+        .header(USER_AGENT, concat!("network_monitor/", env!("CARGO_PKG_VERSION")))
+        .body(Body::empty())
+        .map_err(|e| anyhow!("HTTP 요청 생성 실패: {}", e))?;
+    for (name, value) in headers {
+        let name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| anyhow!("잘못된 헤더 이름 '{}': {}", name, e))?;
+        let value = HeaderValue::from_str(value).map_err(|e| anyhow!("잘못된 헤더 값 '{}': {}", value, e))?;
+        http_request.headers_mut().insert(name, value);
+    }
+    Ok(http_request)
+}
+
+/// 리디렉션한 요청에 보낼 헤더. 다른 출처로 이동하면 인증 정보와 `Host` 헤더를 뺍니다.
+fn redirect_headers(headers: &BTreeMap<String, String>, from: &Url, to: &Url) -> BTreeMap<String, String> {
+    if from.origin() == to.origin() {
+        return headers.clone();
+    }
+    headers
+        .iter()
+        .filter(|(name, _)| !CROSS_ORIGIN_STRIPPED_HEADERS.contains(&name.to_ascii_lowercase().as_str()))
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect()
+}
+
+/// 응답 본문을 최대 `MAX_BODY_BYTES`까지 읽습니다.
+/// 크거나 끝나지 않는 응답도 메모리를 계속 차지하지 않도록 한도에 도달하면 읽기를 멈춥니다.
+async fn read_body(mut body: Body) -> Result<Bytes> {
+    let mut buf = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(|e| anyhow!("HTTP 본문 읽기 실패: {}", e))?;
+        let remaining = MAX_BODY_BYTES - buf.len();
+        buf.extend_from_slice(&chunk[..chunk.len().min(remaining)]);
+        if buf.len() >= MAX_BODY_BYTES {
+            break;
+        }
+    }
+    Ok(Bytes::from(buf))
+}
+
+/// HTTP 요청을 보내고 리디렉션을 따라가며, 마지막 요청의 단계별 시간과 함께 응답을 반환합니다.
+/// 전체 과정이 `timeout_duration` 안에 끝나지 않으면 실패합니다.
+pub async fn send_request(url: &str, request: &HttpRequest<'_>, timeout_duration: Duration) -> Result<HttpResponse> {
+    let mut url = Url::parse(url).map_err(|e| anyhow!("잘못된 URL '{}': {}", url, e))?;
+    let mut method = request.method.clone();
+    let mut headers = request.headers.clone();
+
+    let exchange = async {
+        for _ in 0..=MAX_REDIRECTS {
+            let (response, timing) = send_once(&url, request, &method, &headers, timeout_duration).await?;
+            let status = response.status();
+
+            let location = response
+                .headers()
+                .get(LOCATION)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string);
+            if let Some(location) = location.filter(|_| {
+                status.is_redirection() && !request.stop_on_status.contains(&status.as_u16())
+            }) {
+                let next = url
+                    .join(&location)
+                    .map_err(|e| anyhow!("잘못된 리디렉션 주소 '{}': {}", location, e))?;
+                headers = redirect_headers(&headers, &url, &next);
+                url = next;
+                if status == StatusCode::SEE_OTHER {
+                    method = Method::GET;
+                }
+                continue;
+            }
+
+            let body = if request.read_body {
+                Some(read_body(response.into_body()).await?)
+            } else {
+                None
+            };
+            return Ok(HttpResponse {
+                url: url.clone(),
+                status,
+                body,
+                timing,
+            });
+        }
+        Err(anyhow!("리디렉션이 너무 많음 ({}회 초과)", MAX_REDIRECTS))
+    };
+
+    timeout(timeout_duration, exchange)
+        .await
+        .map_err(|_| anyhow!("HTTP 요청 시간 초과"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+    }
+
+    #[test]
+    fn user_headers_replace_defaults() {
+        let url = Url::parse("http://192.0.2.1:8080/health?full=1").unwrap();
+        let request = build_request(&url, &Method::GET, &headers(&[("Host", "api.internal"), ("X-Token", "a")])).unwrap();
+        assert_eq!(request.uri(), "/health?full=1");
+        let hosts: Vec<_> = request.headers().get_all(HOST).iter().collect();
+        assert_eq!(hosts, vec!["api.internal"]);
+        assert_eq!(request.headers()["x-token"], "a");
+
+        let request = build_request(&url, &Method::GET, &BTreeMap::new()).unwrap();
+        assert_eq!(request.headers()[HOST], "192.0.2.1:8080");
+        assert!(request.headers().contains_key(USER_AGENT));
+    }
+
+    #[test]
+    fn cross_origin_redirects_drop_credentials() {
+        let sent = headers(&[("Authorization", "Bearer x"), ("cookie", "a=b"), ("Host", "api"), ("Accept", "text/plain")]);
+        let from = Url::parse("https://api.internal/login").unwrap();
+
+        let same = redirect_headers(&sent, &from, &Url::parse("https://api.internal/health").unwrap());
+        assert_eq!(same, sent);
+
+        for to in ["https://evil.example/", "http://api.internal/", "https://api.internal:8443/"] {
+            let other = redirect_headers(&sent, &from, &Url::parse(to).unwrap());
+            assert_eq!(other, headers(&[("Accept", "text/plain")]), "{}", to);
+        }
+    }
+}
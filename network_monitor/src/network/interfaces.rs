@@ -0,0 +1,343 @@
+use anyhow::{anyhow, Result};
+use std::fmt;
+use std::net::IpAddr;
+
+/// 인터페이스의 동작 상태입니다 (RFC 2863 operStatus).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperState {
+    Up,
+    Down,
+    Dormant,
+    LowerLayerDown,
+    NotPresent,
+    Testing,
+    Unknown,
+}
+
+impl OperState {
+    /// 리눅스 sysfs `operstate` 값 또는 Windows `Get-NetAdapter` 상태 문자열을 변환합니다.
+    pub fn parse(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "up" => Self::Up,
+            "down" | "disconnected" | "disabled" => Self::Down,
+            "dormant" => Self::Dormant,
+            "lowerlayerdown" => Self::LowerLayerDown,
+            "notpresent" | "not present" => Self::NotPresent,
+            "testing" => Self::Testing,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for OperState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Self::Up => "up",
+            Self::Down => "down",
+            Self::Dormant => "dormant",
+            Self::LowerLayerDown => "lower-layer-down",
+            Self::NotPresent => "not-present",
+            Self::Testing => "testing",
+            Self::Unknown => "unknown",
+        };
+        f.write_str(text)
+    }
+}
+
+/// 인터페이스에 할당된 주소와 프리픽스 길이입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InterfaceAddress {
+    pub address: IpAddr,
+    pub prefix_len: u8,
+}
+
+impl fmt::Display for InterfaceAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.address, self.prefix_len)
+    }
+}
+
+/// 네트워크 인터페이스 한 개의 정보입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkInterface {
+    pub name: String,
+    pub index: u32,
+    /// `aa:bb:cc:dd:ee:ff` 형식 (루프백 등 하드웨어 주소가 없으면 None)
+    pub mac: Option<String>,
+    pub mtu: Option<u32>,
+    pub oper_state: OperState,
+    /// 링크 속도 (Mbps, 알 수 없으면 None)
+    pub speed_mbps: Option<u64>,
+    pub ipv4: Vec<InterfaceAddress>,
+    pub ipv6: Vec<InterfaceAddress>,
+    /// 이 인터페이스를 통하는 기본 경로의 게이트웨이
+    pub gateways: Vec<IpAddr>,
+}
+
+impl NetworkInterface {
+    #[cfg_attr(not(feature = "gui"), allow(dead_code))]
+    pub fn is_up(&self) -> bool {
+        self.oper_state == OperState::Up
+    }
+
+    /// 기본 경로가 이 인터페이스를 사용하는지 여부
+    pub fn has_default_route(&self) -> bool {
+        !self.gateways.is_empty()
+    }
+
+    fn push_address(&mut self, address: InterfaceAddress) {
+        let list = match address.address {
+            IpAddr::V4(_) => &mut self.ipv4,
+            IpAddr::V6(_) => &mut self.ipv6,
+        };
+        if !list.contains(&address) {
+            list.push(address);
+        }
+    }
+}
+
+fn join<T: fmt::Display>(items: &[T]) -> String {
+    items.iter().map(|item| item.to_string()).collect::<Vec<_>>().join(", ")
+}
+
+impl fmt::Display for NetworkInterface {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{} {} [{}]", self.index, self.name, self.oper_state)?;
+        if let Some(mac) = &self.mac {
+            write!(f, " MAC {}", mac)?;
+        }
+        if let Some(mtu) = self.mtu {
+            write!(f, " MTU {}", mtu)?;
+        }
+        if let Some(speed) = self.speed_mbps {
+            write!(f, " {} Mbps", speed)?;
+        }
+        if !self.ipv4.is_empty() {
+            write!(f, ", IPv4 [{}]", join(&self.ipv4))?;
+        }
+        if !self.ipv6.is_empty() {
+            write!(f, ", IPv6 [{}]", join(&self.ipv6))?;
+        }
+        if !self.gateways.is_empty() {
+            write!(f, ", 게이트웨이 [{}]", join(&self.gateways))?;
+        }
+        Ok(())
+    }
+}
+
+/// 시스템의 네트워크 인터페이스 목록을 인덱스 순서로 가져옵니다.
+/// 리눅스는 sysfs와 `getifaddrs`, 라우팅 테이블을 사용합니다.
+#[cfg(target_os = "linux")]
+pub fn list() -> Result<Vec<NetworkInterface>> {
+    use std::fs;
+    use std::path::Path;
+
+    fn read_attr(dir: &Path, name: &str) -> Option<String> {
+        fs::read_to_string(dir.join(name)).ok().map(|value| value.trim().to_string())
+    }
+
+    let mut interfaces = Vec::new();
+    let entries = fs::read_dir("/sys/class/net").map_err(|e| anyhow!("/sys/class/net 읽기 실패: {}", e))?;
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Some(index) = read_attr(&dir, "ifindex").and_then(|v| v.parse().ok()) else {
+            continue;
+        };
+
+        interfaces.push(NetworkInterface {
+            name,
+            index,
+            mac: read_attr(&dir, "address").filter(|mac| !mac.is_empty() && mac != "00:00:00:00:00:00"),
+            mtu: read_attr(&dir, "mtu").and_then(|v| v.parse().ok()),
+            oper_state: read_attr(&dir, "operstate").map_or(OperState::Unknown, |v| OperState::parse(&v)),
+            // 링크가 내려가 있거나 가상 인터페이스이면 읽기 실패 또는 -1
+            speed_mbps: read_attr(&dir, "speed").and_then(|v| v.parse::<i64>().ok()).and_then(|v| u64::try_from(v).ok()),
+            ipv4: Vec::new(),
+            ipv6: Vec::new(),
+            gateways: Vec::new(),
+        });
+    }
+
+    for (name, address) in linux_addresses()? {
+        // `eth0:1` 같은 별칭 레이블은 실제 인터페이스에 합침
+        let name = name.split(':').next().unwrap_or(&name);
+        if let Some(interface) = interfaces.iter_mut().find(|i| i.name == name) {
+            interface.push_address(address);
+        }
+    }
+    for route in super::discovery::default_routes()? {
+        if let Some(interface) = interfaces.iter_mut().find(|i| i.name == route.interface) {
+            if !interface.gateways.contains(&route.gateway) {
+                interface.gateways.push(route.gateway);
+            }
+        }
+    }
+
+    interfaces.sort_by_key(|i| i.index);
+    Ok(interfaces)
+}
+
+/// `getifaddrs`로 인터페이스별 IPv4/IPv6 주소를 가져옵니다.
+#[cfg(target_os = "linux")]
+fn linux_addresses() -> Result<Vec<(String, InterfaceAddress)>> {
+    use std::ffi::CStr;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    /// sockaddr에서 IP 주소를 꺼냅니다.
+    ///
+    /// # Safety
+    /// `addr`은 null이거나 유효한 sockaddr를 가리켜야 합니다.
+    unsafe fn ip_from_sockaddr(addr: *const libc::sockaddr) -> Option<IpAddr> {
+        if addr.is_null() {
+            return None;
+        }
+        match i32::from((*addr).sa_family) {
+            libc::AF_INET => {
+                let addr = &*(addr as *const libc::sockaddr_in);
+                Some(IpAddr::V4(Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr))))
+            }
+            libc::AF_INET6 => {
+                let addr = &*(addr as *const libc::sockaddr_in6);
+                Some(IpAddr::V6(Ipv6Addr::from(addr.sin6_addr.s6_addr)))
+            }
+            _ => None,
+        }
+    }
+
+    fn prefix_len(netmask: Option<IpAddr>) -> u8 {
+        match netmask {
+            Some(IpAddr::V4(mask)) => u32::from(mask).count_ones() as u8,
+            Some(IpAddr::V6(mask)) => u128::from(mask).count_ones() as u8,
+            None => 0,
+        }
+    }
+
+    let mut addresses = Vec::new();
+    let mut ifaddrs: *mut libc::ifaddrs = std::ptr::null_mut();
+    // SAFETY: getifaddrs가 성공하면 목록을 할당하고, 아래에서 freeifaddrs로 해제함
+    if unsafe { libc::getifaddrs(&mut ifaddrs) } != 0 {
+        return Err(anyhow!("getifaddrs 실패: {}", std::io::Error::last_os_error()));
+    }
+
+    let mut current = ifaddrs;
+    while !current.is_null() {
+        // SAFETY: current는 getifaddrs가 반환한 연결 리스트의 유효한 노드
+        unsafe {
+            let entry = &*current;
+            if let Some(address) = ip_from_sockaddr(entry.ifa_addr) {
+                let name = CStr::from_ptr(entry.ifa_name).to_string_lossy().to_string();
+                let prefix_len = prefix_len(ip_from_sockaddr(entry.ifa_netmask));
+                addresses.push((name, InterfaceAddress { address, prefix_len }));
+            }
+            current = entry.ifa_next;
+        }
+    }
+    // SAFETY: getifaddrs로 얻은 포인터를 한 번만 해제
+    unsafe { libc::freeifaddrs(ifaddrs) };
+
+    Ok(addresses)
+}
+
+/// 시스템의 네트워크 인터페이스 목록을 인덱스 순서로 가져옵니다.
+/// Windows는 `Get-NetAdapter`, `Get-NetIPAddress`, `Get-NetRoute` 결과를 JSON으로 받아 변환합니다.
+#[cfg(windows)]
+pub fn list() -> Result<Vec<NetworkInterface>> {
+    use serde::Deserialize;
+    use std::process::Command;
+    use std::str::FromStr;
+
+    const SCRIPT: &str = r#"
+$adapters = @(Get-NetAdapter | ForEach-Object {
+    $index = $_.ifIndex
+    [PSCustomObject]@{
+        Name = $_.Name
+        Index = $index
+        Mac = $_.MacAddress
+        Mtu = $_.MtuSize
+        Status = "$($_.Status)"
+        Speed = $_.Speed
+        Addresses = @(Get-NetIPAddress -InterfaceIndex $index -ErrorAction SilentlyContinue |
+            ForEach-Object { "$($_.IPAddress)/$($_.PrefixLength)" })
+        Gateways = @(Get-NetRoute -InterfaceIndex $index -DestinationPrefix '0.0.0.0/0','::/0' -ErrorAction SilentlyContinue |
+            ForEach-Object { $_.NextHop })
+    }
+})
+ConvertTo-Json -InputObject $adapters -Depth 3
+"#;
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct RawAdapter {
+        name: String,
+        index: u32,
+        mac: Option<String>,
+        mtu: Option<u32>,
+        status: String,
+        /// bits per second
+        speed: Option<u64>,
+        #[serde(default)]
+        addresses: Vec<String>,
+        #[serde(default)]
+        gateways: Vec<String>,
+    }
+
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-Command", SCRIPT])
+        .output()
+        .map_err(|e| anyhow!("네트워크 인터페이스 정보 가져오기 실패: {}", e))?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "네트워크 인터페이스 정보 가져오기 오류: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    let adapters: Vec<RawAdapter> = serde_json::from_slice(&output.stdout)
+        .map_err(|e| anyhow!("네트워크 인터페이스 정보 해석 실패: {}", e))?;
+
+    let mut interfaces: Vec<NetworkInterface> = adapters
+        .into_iter()
+        .map(|raw| {
+            let mut interface = NetworkInterface {
+                name: raw.name,
+                index: raw.index,
+                mac: raw
+                    .mac
+                    .filter(|mac| !mac.is_empty())
+                    .map(|mac| mac.replace('-', ":").to_lowercase()),
+                mtu: raw.mtu,
+                oper_state: OperState::parse(&raw.status),
+                speed_mbps: raw.speed.filter(|speed| *speed > 0).map(|speed| speed / 1_000_000),
+                ipv4: Vec::new(),
+                ipv6: Vec::new(),
+                gateways: raw
+                    .gateways
+                    .iter()
+                    .filter_map(|gateway| IpAddr::from_str(gateway).ok())
+                    .filter(|gateway| !gateway.is_unspecified())
+                    .collect(),
+            };
+            for address in &raw.addresses {
+                // 링크 로컬 IPv6의 `%12` 같은 영역 표기는 제거
+                let Some((ip, prefix)) = address.split_once('/') else {
+                    continue;
+                };
+                let ip = ip.split('%').next().unwrap_or(ip);
+                if let (Ok(address), Ok(prefix_len)) = (IpAddr::from_str(ip), prefix.parse()) {
+                    interface.push_address(InterfaceAddress { address, prefix_len });
+                }
+            }
+            interface
+        })
+        .collect();
+
+    interfaces.sort_by_key(|i| i.index);
+    Ok(interfaces)
+}
+
+/// 시스템의 네트워크 인터페이스 목록을 가져옵니다.
+#[cfg(not(any(target_os = "linux", windows)))]
+pub fn list() -> Result<Vec<NetworkInterface>> {
+    Err(anyhow!("이 플랫폼에서는 네트워크 인터페이스 목록을 지원하지 않습니다"))
+}
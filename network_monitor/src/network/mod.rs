@@ -1,37 +1,156 @@
+pub mod discovery;
+pub mod events;
+pub mod http;
+pub mod interfaces;
+pub mod pinger;
+pub mod recovery;
+pub mod stats;
+pub mod template;
+pub mod timing;
+
+use std::collections::BTreeMap;
+#[cfg(windows)]
 use std::process::Command;
 use std::time::{Duration, Instant};
-use tokio::net::TcpStream;
-use tokio::process::Command as TokioCommand;
-use tokio::time::timeout;
-use winping::{Buffer, Pinger};
+use tokio::net::lookup_host;
 use anyhow::{Result, anyhow};
 use std::net::IpAddr;
 use std::str::FromStr;
+use pinger::Pinger;
+use crate::config::AddressFamily;
+
+pub use stats::PingStats;
+pub use interfaces::NetworkInterface;
+pub use recovery::{ExecutionResult, RecoveryPlan};
+pub use template::TemplateContext;
+pub use timing::ConnectionTiming;
+
+/// 주소 하나에 대한 핑 결과입니다. 듀얼 스택 대상은 패밀리별로 하나씩 생성됩니다.
+#[derive(Debug, Clone)]
+pub struct AddressPing {
+    pub address: IpAddr,
+    pub result: Result<PingStats, String>,
+}
+
+impl AddressPing {
+    pub fn family_label(&self) -> &'static str {
+        family_label(&self.address)
+    }
+}
+
+/// 주소의 패밀리 이름을 반환합니다.
+pub fn family_label(addr: &IpAddr) -> &'static str {
+    match addr {
+        IpAddr::V4(_) => "IPv4",
+        IpAddr::V6(_) => "IPv6",
+    }
+}
+
+/// 호스트 이름 또는 IP 주소를 주소 패밀리 설정에 맞게 해석합니다.
+///
+/// `V4`/`V6`은 해당 패밀리 주소 하나를 우선 반환하고 없으면 다른 패밀리로 대체하며,
+/// `Both`는 존재하는 패밀리마다 주소를 하나씩 반환합니다.
+pub async fn resolve_host(host: &str, family: AddressFamily) -> Result<Vec<IpAddr>> {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if let Ok(ip_addr) = IpAddr::from_str(host) {
+        return Ok(vec![ip_addr]);
+    }
+
+    let addrs: Vec<IpAddr> = lookup_host((host, 0))
+        .await
+        .map_err(|e| anyhow!("호스트 이름 해석 실패 ({}): {}", host, e))?
+        .map(|addr| addr.ip())
+        .collect();
+
+    let v4 = addrs.iter().find(|addr| addr.is_ipv4()).copied();
+    let v6 = addrs.iter().find(|addr| addr.is_ipv6()).copied();
+    let selected: Vec<IpAddr> = match family {
+        AddressFamily::V4 => v4.or(v6).into_iter().collect(),
+        AddressFamily::V6 => v6.or(v4).into_iter().collect(),
+        AddressFamily::Both => v4.into_iter().chain(v6).collect(),
+    };
+
+    if selected.is_empty() {
+        return Err(anyhow!("호스트 '{}'의 주소를 찾을 수 없음", host));
+    }
+    Ok(selected)
+}
 
 /// 호스트에 ICMP 핑 테스트를 수행합니다.
 pub async fn ping_host(host: &str, timeout_duration: Duration) -> Result<Duration> {
     // 호스트 문자열을 IpAddr로 변환
-    let ip_addr = IpAddr::from_str(host).map_err(|e| anyhow!("IP 주소 변환 실패: {}", e))?;
-    
-    // Windows용 ping 구현
-    let pinger = Pinger::new().map_err(|e| anyhow!("Pinger 생성 실패: {}", e))?;
-    let mut buffer = Buffer::new();
-    
-    let start = Instant::now();
-    let _result = pinger.send(ip_addr, &mut buffer).map_err(|e| anyhow!("Ping 전송 실패: {}", e))?;
+    let ip_addr = resolve_host(host, AddressFamily::default()).await?[0];
     
-    // ping이 성공하면 elapsed 시간을 반환
-    Ok(start.elapsed())
+    // 플랫폼별 pinger는 블로킹 소켓을 사용하므로 별도 스레드에서 실행
+    tokio::task::spawn_blocking(move || {
+        let pinger = pinger::create_pinger()?;
+        pinger.ping(ip_addr, timeout_duration)
+    })
+    .await
+    .map_err(|e| anyhow!("Ping 작업 실행 실패: {}", e))?
 }
 
-/// 지정된 호스트와 포트에 TCP 연결을 시도합니다.
-pub async fn check_port(host: &str, port: u16, timeout_duration: Duration) -> Result<()> {
-    let addr = format!("{}:{}", host, port);
-    match timeout(timeout_duration, TcpStream::connect(&addr)).await {
-        Ok(Ok(_)) => Ok(()),
-        Ok(Err(e)) => Err(anyhow!("포트 연결 실패: {}", e)),
-        Err(_) => Err(anyhow!("포트 연결 시간 초과")),
+/// 주소에 `count`개의 에코 요청을 `interval` 간격으로 보내고 통계를 반환합니다.
+/// 응답을 하나도 받지 못하면 마지막 오류를 반환합니다.
+pub async fn ping_addr_stats(
+    ip_addr: IpAddr,
+    count: u32,
+    interval: Duration,
+    timeout_duration: Duration,
+) -> Result<PingStats> {
+    let count = count.max(1);
+
+    let (samples, last_error) = tokio::task::spawn_blocking(move || -> Result<_> {
+        let pinger = pinger::create_pinger()?;
+        let mut samples = Vec::with_capacity(count as usize);
+        let mut last_error = None;
+
+        for seq in 0..count {
+            let sent_at = Instant::now();
+            match pinger.ping(ip_addr, timeout_duration) {
+                Ok(rtt) => samples.push(rtt),
+                Err(e) => last_error = Some(e),
+            }
+
+            if seq + 1 < count {
+                std::thread::sleep(interval.saturating_sub(sent_at.elapsed()));
+            }
+        }
+
+        Ok((samples, last_error))
+    })
+    .await
+    .map_err(|e| anyhow!("Ping 작업 실행 실패: {}", e))??;
+
+    if samples.is_empty() {
+        let reason = last_error.map_or_else(|| "응답 없음".to_string(), |e| e.to_string());
+        return Err(anyhow!("모든 패킷 손실 ({}/{}): {}", count, count, reason));
     }
+
+    Ok(PingStats::from_samples(count, &samples))
+}
+
+/// 호스트를 해석한 뒤 주소마다 다중 패킷 핑을 수행합니다.
+pub async fn ping_host_stats(
+    host: &str,
+    family: AddressFamily,
+    count: u32,
+    interval: Duration,
+    timeout_duration: Duration,
+) -> Result<Vec<AddressPing>> {
+    let mut results = Vec::new();
+    for address in resolve_host(host, family).await? {
+        let result = ping_addr_stats(address, count, interval, timeout_duration)
+            .await
+            .map_err(|e| e.to_string());
+        results.push(AddressPing { address, result });
+    }
+    Ok(results)
+}
+
+/// 지정된 호스트와 포트에 TCP 연결을 시도하고 단계별 소요 시간을 반환합니다.
+pub async fn check_port(host: &str, port: u16, timeout_duration: Duration) -> Result<ConnectionTiming> {
+    timing::connect(host, port, timeout_duration).await.map(|(_, timing)| timing)
 }
 
 /// 네트워크 연결 테스트를 수행합니다.
@@ -39,9 +158,16 @@ pub async fn test_connection(host: &str) -> Result<()> {
     // 기본 타임아웃 설정
     let timeout_duration = Duration::from_secs(5);
     
-    // ICMP 핑 테스트
-    match ping_host(host, timeout_duration).await {
-        Ok(rtt) => println!("ICMP 핑 성공: {}ms", rtt.as_millis()),
+    // ICMP 핑 테스트 (듀얼 스택 호스트는 패밀리별로 확인)
+    match ping_host_stats(host, AddressFamily::Both, 4, Duration::from_secs(1), timeout_duration).await {
+        Ok(results) => {
+            for ping in &results {
+                match &ping.result {
+                    Ok(stats) => println!("ICMP 핑 성공 ({} {}): {}", ping.family_label(), ping.address, stats),
+                    Err(e) => println!("ICMP 핑 실패 ({} {}): {}", ping.family_label(), ping.address, e),
+                }
+            }
+        }
         Err(e) => println!("ICMP 핑 실패: {}", e),
     }
     
@@ -49,93 +175,126 @@ pub async fn test_connection(host: &str) -> Result<()> {
     let common_ports = [80, 443, 8080];
     for port in common_ports {
         match check_port(host, port, timeout_duration).await {
-            Ok(_) => println!("포트 {} 연결 성공", port),
+            Ok(timing) => println!("포트 {} 연결 성공 ({}): {}", port, timing.peer, timing),
             Err(e) => println!("포트 {} 연결 실패: {}", port, e),
         }
     }
+
+    // HTTP/HTTPS 요청 단계별 시간
+    let headers = BTreeMap::new();
+    let request = http::HttpRequest {
+        method: hyper::Method::GET,
+        headers: &headers,
+        verify_tls: false,
+        stop_on_status: &[],
+        read_body: false,
+    };
+    let url_host = if host.contains(':') && !host.starts_with('[') {
+        format!("[{}]", host)
+    } else {
+        host.to_string()
+    };
+    for scheme in ["http", "https"] {
+        let url = format!("{}://{}/", scheme, url_host);
+        match http::send_request(&url, &request, timeout_duration).await {
+            Ok(response) => println!(
+                "{} → {} (총 {:.2} ms): {}",
+                response.url,
+                response.status,
+                stats::as_millis_f64(response.timing.total()),
+                response.timing
+            ),
+            Err(e) => println!("{} 요청 실패: {}", url, e),
+        }
+    }
     
     // 네트워크 인터페이스 정보 출력
     match get_network_interfaces() {
-        Ok(output) => println!("네트워크 인터페이스 정보:\n{}", output),
+        Ok(interfaces) => {
+            println!("네트워크 인터페이스 정보 (* 기본 경로):");
+            for interface in interfaces {
+                let marker = if interface.has_default_route() { "*" } else { " " };
+                println!("{} {}", marker, interface);
+            }
+        }
         Err(e) => println!("네트워크 인터페이스 정보 가져오기 실패: {}", e),
     }
     
     Ok(())
 }
 
-/// 시스템 명령어를 실행하고 결과를 반환합니다.
-pub async fn execute_command(cmd: &str) -> Result<String> {
-    let output = TokioCommand::new("powershell")
-        .args(["-Command", cmd])
-        .output()
-        .await
-        .map_err(|e| anyhow!("명령어 실행 실패: {}", e))?;
-    
-    if output.status.success() {
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        Ok(stdout)
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-        Err(anyhow!("명령어 실행 오류: {}", stderr))
-    }
+/// 네트워크 인터페이스 정보를 가져옵니다.
+pub fn get_network_interfaces() -> Result<Vec<NetworkInterface>> {
+    interfaces::list()
 }
 
-/// 네트워크 인터페이스 정보를 가져옵니다.
-pub fn get_network_interfaces() -> Result<String> {
-    let output = Command::new("powershell")
-        .args(["-Command", "Get-NetAdapter | Format-Table -AutoSize"])
-        .output()
-        .map_err(|e| anyhow!("네트워크 인터페이스 정보 가져오기 실패: {}", e))?;
-    
-    if output.status.success() {
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        Ok(stdout)
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-        Err(anyhow!("네트워크 인터페이스 정보 가져오기 오류: {}", stderr))
-    }
+/// 기본 경로가 사용하는 인터페이스 이름을 가져옵니다.
+pub fn default_route_interface() -> Option<String> {
+    interfaces::list()
+        .ok()?
+        .into_iter()
+        .find(|interface| interface.has_default_route())
+        .map(|interface| interface.name)
 }
 
-/// 네트워크 인터페이스를 재시작합니다.
-pub async fn restart_network_interface(interface_name: &str) -> Result<()> {
-    let cmd = format!("Restart-NetAdapter -Name '{}' -Confirm:$false", interface_name);
-    
-    match execute_command(&cmd).await {
-        Ok(_) => {
-            println!("네트워크 인터페이스 '{}' 재시작 성공", interface_name);
-            Ok(())
-        }
-        Err(e) => {
-            println!("네트워크 인터페이스 '{}' 재시작 실패: {}", interface_name, e);
-            Err(e)
+/// 시스템에 설정된 DNS 서버 목록을 가져옵니다.
+/// `/etc/resolv.conf`가 systemd-resolved 스텁(127.0.0.53)만 가리키면 실제 업스트림 서버 목록을 사용합니다.
+#[cfg(not(windows))]
+pub fn system_dns_servers() -> Result<Vec<IpAddr>> {
+    const RESOLVED_STUBS: [&str; 2] = ["127.0.0.53", "127.0.0.54"];
+    const RESOLVED_UPSTREAM: &str = "/run/systemd/resolve/resolv.conf";
+
+    let content = std::fs::read_to_string("/etc/resolv.conf")
+        .map_err(|e| anyhow!("/etc/resolv.conf 읽기 실패: {}", e))?;
+    let servers = parse_resolv_conf(&content);
+
+    let stub_only = !servers.is_empty()
+        && servers.iter().all(|server| RESOLVED_STUBS.contains(&server.to_string().as_str()));
+    if stub_only {
+        if let Ok(upstream) = std::fs::read_to_string(RESOLVED_UPSTREAM) {
+            let upstream = parse_resolv_conf(&upstream);
+            if !upstream.is_empty() {
+                return Ok(upstream);
+            }
         }
     }
+    Ok(servers)
 }
 
-/// DNS 캐시를 초기화합니다.
-pub async fn flush_dns() -> Result<()> {
-    match execute_command("Clear-DnsClientCache").await {
-        Ok(_) => {
-            println!("DNS 캐시 초기화 성공");
-            Ok(())
-        }
-        Err(e) => {
-            println!("DNS 캐시 초기화 실패: {}", e);
-            Err(e)
+/// 시스템에 설정된 DNS 서버 목록을 가져옵니다.
+#[cfg(windows)]
+pub fn system_dns_servers() -> Result<Vec<IpAddr>> {
+    let output = Command::new("powershell")
+        .args([
+            "-Command",
+            "Get-DnsClientServerAddress | Select-Object -ExpandProperty ServerAddresses",
+        ])
+        .output()
+        .map_err(|e| anyhow!("DNS 서버 정보 가져오기 실패: {}", e))?;
+
+    let mut servers = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        if let Ok(addr) = IpAddr::from_str(line.trim()) {
+            if !servers.contains(&addr) {
+                servers.push(addr);
+            }
         }
     }
+    Ok(servers)
 }
 
-/// IP 설정을 갱신합니다.
-pub async fn renew_ip() -> Result<()> {
-    match execute_command("ipconfig /renew").await {
-        Ok(_) => {
-            println!("IP 설정 갱신 성공");
-            Ok(())
-        }
-        Err(e) => {
-            println!("IP 설정 갱신 실패: {}", e);
-            Err(e)
-        }
-    }
+/// resolv.conf 형식에서 `nameserver` 항목을 추출합니다.
+#[cfg(not(windows))]
+pub fn parse_resolv_conf(content: &str) -> Vec<IpAddr> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            match (fields.next(), fields.next()) {
+                // 링크 로컬 IPv6 주소의 `%eth0` 같은 영역 표기는 제거
+                (Some("nameserver"), Some(addr)) => IpAddr::from_str(addr.split('%').next()?).ok(),
+                _ => None,
+            }
+        })
+        .collect()
 }
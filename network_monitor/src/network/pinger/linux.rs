@@ -0,0 +1,226 @@
+use super::Pinger;
+use anyhow::{anyhow, Result};
+use socket2::{Domain, Protocol, SockAddr, Socket, Type};
+use std::io::{ErrorKind, Read};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::time::{Duration, Instant};
+
+const ICMPV4_ECHO_REQUEST: u8 = 8;
+const ICMPV4_ECHO_REPLY: u8 = 0;
+const ICMPV6_ECHO_REQUEST: u8 = 128;
+const ICMPV6_ECHO_REPLY: u8 = 129;
+const PAYLOAD: &[u8] = b"network_monitor ping payload";
+
+static SEQUENCE: AtomicU16 = AtomicU16::new(1);
+
+/// Linux용 ICMP 구현입니다.
+///
+/// 권한 없이 사용할 수 있는 ICMP 데이터그램 소켓(`net.ipv4.ping_group_range`)을
+/// 우선 사용하고, 허용되지 않는 환경에서는 raw 소켓(CAP_NET_RAW 필요)으로 대체합니다.
+pub struct LinuxPinger {
+    identifier: u16,
+}
+
+impl LinuxPinger {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            identifier: (std::process::id() & 0xffff) as u16,
+        })
+    }
+
+    /// 데이터그램 소켓을 먼저 시도하고 실패하면 raw 소켓을 엽니다.
+    fn open_socket(addr: &IpAddr) -> Result<(Socket, bool)> {
+        let (domain, protocol) = match addr {
+            IpAddr::V4(_) => (Domain::IPV4, Protocol::ICMPV4),
+            IpAddr::V6(_) => (Domain::IPV6, Protocol::ICMPV6),
+        };
+
+        match Socket::new(domain, Type::DGRAM, Some(protocol)) {
+            Ok(socket) => Ok((socket, false)),
+            Err(dgram_err) => Socket::new(domain, Type::RAW, Some(protocol))
+                .map(|socket| (socket, true))
+                .map_err(|raw_err| {
+                    anyhow!(
+                        "ICMP 소켓 생성 실패 (datagram: {}, raw: {})",
+                        dgram_err,
+                        raw_err
+                    )
+                }),
+        }
+    }
+}
+
+impl Pinger for LinuxPinger {
+    fn ping(&self, addr: IpAddr, timeout: Duration) -> Result<Duration> {
+        let (mut socket, raw) = Self::open_socket(&addr)?;
+        socket
+            .connect(&SockAddr::from(SocketAddr::new(addr, 0)))
+            .map_err(|e| anyhow!("ICMP 소켓 연결 실패: {}", e))?;
+
+        let sequence = SEQUENCE.fetch_add(1, Ordering::Relaxed);
+        let packet = build_echo_request(&addr, self.identifier, sequence);
+
+        let start = Instant::now();
+        socket
+            .send(&packet)
+            .map_err(|e| anyhow!("Ping 전송 실패: {}", e))?;
+
+        // 데이터그램 소켓은 커널이 식별자를 덮어쓰므로 순번만 비교합니다.
+        let expected_id = if raw { Some(self.identifier) } else { None };
+        let deadline = start + timeout;
+        let mut buf = [0u8; 1500];
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(anyhow!("Ping 응답 시간 초과"));
+            }
+            socket
+                .set_read_timeout(Some(remaining))
+                .map_err(|e| anyhow!("ICMP 소켓 설정 실패: {}", e))?;
+
+            let len = match socket.read(&mut buf) {
+                Ok(len) => len,
+                Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                    return Err(anyhow!("Ping 응답 시간 초과"));
+                }
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(anyhow!("Ping 응답 수신 실패: {}", e)),
+            };
+
+            // raw IPv4 소켓은 IP 헤더를 포함해서 전달합니다.
+            let icmp = if raw && addr.is_ipv4() {
+                let header_len = usize::from(buf[0] & 0x0f) * 4;
+                match buf.get(header_len..len) {
+                    Some(icmp) => icmp,
+                    None => continue,
+                }
+            } else {
+                &buf[..len]
+            };
+
+            if is_echo_reply(&addr, icmp, sequence, expected_id) {
+                return Ok(start.elapsed());
+            }
+        }
+    }
+}
+
+fn build_echo_request(addr: &IpAddr, identifier: u16, sequence: u16) -> Vec<u8> {
+    let icmp_type = if addr.is_ipv4() {
+        ICMPV4_ECHO_REQUEST
+    } else {
+        ICMPV6_ECHO_REQUEST
+    };
+
+    let mut packet = Vec::with_capacity(8 + PAYLOAD.len());
+    packet.push(icmp_type);
+    packet.push(0);
+    packet.extend_from_slice(&[0, 0]);
+    packet.extend_from_slice(&identifier.to_be_bytes());
+    packet.extend_from_slice(&sequence.to_be_bytes());
+    packet.extend_from_slice(PAYLOAD);
+
+    // ICMPv6 체크섬은 커널이 채웁니다.
+    if addr.is_ipv4() {
+        let checksum = checksum(&packet);
+        packet[2..4].copy_from_slice(&checksum.to_be_bytes());
+    }
+
+    packet
+}
+
+fn is_echo_reply(addr: &IpAddr, icmp: &[u8], sequence: u16, identifier: Option<u16>) -> bool {
+    if icmp.len() < 8 {
+        return false;
+    }
+
+    let reply_type = if addr.is_ipv4() {
+        ICMPV4_ECHO_REPLY
+    } else {
+        ICMPV6_ECHO_REPLY
+    };
+
+    icmp[0] == reply_type
+        && u16::from_be_bytes([icmp[6], icmp[7]]) == sequence
+        && identifier.is_none_or(|id| u16::from_be_bytes([icmp[4], icmp[5]]) == id)
+}
+
+fn checksum(data: &[u8]) -> u16 {
+    let mut sum = data
+        .chunks(2)
+        .map(|chunk| match chunk {
+            [hi, lo] => u32::from(u16::from_be_bytes([*hi, *lo])),
+            [hi] => u32::from(*hi) << 8,
+            _ => 0,
+        })
+        .sum::<u32>();
+
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+
+    !(sum as u16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    const V4: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1));
+    const V6: IpAddr = IpAddr::V6(Ipv6Addr::LOCALHOST);
+
+    /// 요청 패킷을 같은 식별자/순번의 응답 패킷으로 바꿉니다.
+    fn reply_to(addr: &IpAddr, identifier: u16, sequence: u16) -> Vec<u8> {
+        let mut packet = build_echo_request(addr, identifier, sequence);
+        packet[0] = if addr.is_ipv4() { ICMPV4_ECHO_REPLY } else { ICMPV6_ECHO_REPLY };
+        packet
+    }
+
+    #[test]
+    fn checksum_matches_rfc1071_example() {
+        // RFC 1071 4.1절: 1의 보수 합 0xddf2
+        assert_eq!(checksum(&[0x00, 0x01, 0xf2, 0x03, 0xf4, 0xf5, 0xf6, 0xf7]), !0xddf2);
+        // 홀수 길이는 마지막 바이트 뒤에 0을 채운 것과 같습니다.
+        assert_eq!(checksum(&[0x00, 0x01, 0xf2]), checksum(&[0x00, 0x01, 0xf2, 0x00]));
+    }
+
+    #[test]
+    fn ipv4_echo_request_has_valid_checksum() {
+        let packet = build_echo_request(&V4, 0x1234, 7);
+        assert_eq!(packet[0], ICMPV4_ECHO_REQUEST);
+        assert_eq!(&packet[4..8], &[0x12, 0x34, 0x00, 0x07]);
+        assert_eq!(checksum(&packet), 0);
+    }
+
+    #[test]
+    fn ipv6_echo_request_leaves_checksum_to_kernel() {
+        let packet = build_echo_request(&V6, 0x1234, 7);
+        assert_eq!(packet[0], ICMPV6_ECHO_REQUEST);
+        assert_eq!(&packet[2..4], &[0, 0]);
+    }
+
+    #[test]
+    fn rejects_wrong_type_sequence_or_short_packet() {
+        for addr in [V4, V6] {
+            let reply = reply_to(&addr, 1, 7);
+            assert!(is_echo_reply(&addr, &reply, 7, None));
+            assert!(!is_echo_reply(&addr, &reply, 8, None));
+            assert!(!is_echo_reply(&addr, &build_echo_request(&addr, 1, 7), 7, None));
+            assert!(!is_echo_reply(&addr, &reply[..7], 7, None));
+        }
+        // IPv4 응답 유형을 IPv6 대상에서 받으면 응답이 아닙니다.
+        assert!(!is_echo_reply(&V6, &reply_to(&V4, 1, 7), 7, None));
+    }
+
+    #[test]
+    fn identifier_checked_only_for_raw_sockets() {
+        let reply = reply_to(&V4, 0xbeef, 7);
+        // 데이터그램 소켓: 커널이 바꾼 식별자는 무시
+        assert!(is_echo_reply(&V4, &reply, 7, None));
+        // raw 소켓: 식별자가 일치해야 함
+        assert!(is_echo_reply(&V4, &reply, 7, Some(0xbeef)));
+        assert!(!is_echo_reply(&V4, &reply, 7, Some(0x1234)));
+    }
+}
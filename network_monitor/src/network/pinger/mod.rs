@@ -0,0 +1,24 @@
+use anyhow::Result;
+use std::net::IpAddr;
+use std::time::Duration;
+
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(windows)]
+mod windows;
+
+#[cfg(target_os = "linux")]
+pub use linux::LinuxPinger as PlatformPinger;
+#[cfg(windows)]
+pub use windows::WindowsPinger as PlatformPinger;
+
+/// 플랫폼별 ICMP 에코 구현이 제공하는 공통 인터페이스입니다.
+pub trait Pinger {
+    /// 지정된 주소로 에코 요청을 한 번 보내고 응답 시간을 반환합니다.
+    fn ping(&self, addr: IpAddr, timeout: Duration) -> Result<Duration>;
+}
+
+/// 컴파일 대상 플랫폼에 맞는 Pinger를 생성합니다.
+pub fn create_pinger() -> Result<PlatformPinger> {
+    PlatformPinger::new()
+}
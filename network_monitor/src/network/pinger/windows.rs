@@ -0,0 +1,31 @@
+use super::Pinger;
+use anyhow::{anyhow, Result};
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+use winping::{Buffer, Pinger as WinPinger};
+
+/// `winping`(IcmpSendEcho)을 사용하는 Windows 구현입니다.
+pub struct WindowsPinger {
+    pinger: WinPinger,
+}
+
+impl WindowsPinger {
+    pub fn new() -> Result<Self> {
+        let pinger = WinPinger::new().map_err(|e| anyhow!("Pinger 생성 실패: {}", e))?;
+        Ok(Self { pinger })
+    }
+}
+
+impl Pinger for WindowsPinger {
+    fn ping(&self, addr: IpAddr, timeout: Duration) -> Result<Duration> {
+        let mut pinger = self.pinger.clone();
+        pinger.set_timeout(timeout.as_millis().min(u32::MAX as u128) as u32);
+        let mut buffer = Buffer::new();
+
+        let start = Instant::now();
+        pinger.send(addr, &mut buffer).map_err(|e| anyhow!("Ping 전송 실패: {}", e))?;
+
+        // ping이 성공하면 elapsed 시간을 반환
+        Ok(start.elapsed())
+    }
+}
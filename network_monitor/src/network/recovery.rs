@@ -0,0 +1,475 @@
+use super::template::{self, TemplateContext};
+use crate::config::{Config, RecoveryAction, RecoveryKind, Shell};
+use anyhow::{anyhow, Result};
+use std::fmt;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::process::{Child, Command as TokioCommand};
+use tokio::task::JoinHandle;
+use tokio::time;
+
+/// PowerShell 실행 파일 (Windows 외에는 PowerShell 7)
+#[cfg(windows)]
+const POWERSHELL: &str = "powershell";
+#[cfg(not(windows))]
+const POWERSHELL: &str = "pwsh";
+
+/// 프로세스가 끝난 뒤 남은 출력을 기다리는 시간 (백그라운드로 남은 하위 프로세스가 파이프를 잡고 있는 경우)
+const OUTPUT_DRAIN_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// 명령 하나의 실행 결과입니다.
+#[derive(Debug, Clone)]
+pub struct CommandResult {
+    /// 실행한 명령 (표시용)
+    pub command: String,
+    /// 종료 코드 (시간 초과로 종료했거나 시그널로 종료되면 None)
+    pub exit_code: Option<i32>,
+    pub duration: Duration,
+    /// 표준 출력 (max_output_bytes까지)
+    pub stdout: String,
+    /// 표준 오류 (max_output_bytes까지)
+    pub stderr: String,
+    /// 출력이 max_output_bytes를 넘어 잘렸는지 여부
+    pub truncated: bool,
+    pub timed_out: bool,
+    /// 프로세스를 시작하지 못한 경우의 오류
+    pub error: Option<String>,
+    /// 허용한 종료 코드로 끝났는지 여부
+    pub success: bool,
+}
+
+impl fmt::Display for CommandResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.command)?;
+        let seconds = self.duration.as_secs_f64();
+        match (&self.error, self.timed_out, self.exit_code) {
+            (Some(error), _, _) => write!(f, "{}", error),
+            (None, true, _) => write!(f, "시간 초과로 종료 ({:.1}초)", seconds),
+            (None, false, Some(code)) => write!(f, "종료 코드 {} ({:.1}초)", code, seconds),
+            (None, false, None) => write!(f, "시그널로 종료 ({:.1}초)", seconds),
+        }
+    }
+}
+
+/// 복구 작업 하나의 실행 결과입니다. 대안을 시도한 경우 실행한 명령마다 결과가 남습니다.
+#[derive(Debug, Clone)]
+pub struct ExecutionResult {
+    pub steps: Vec<CommandResult>,
+    pub success: bool,
+}
+
+impl fmt::Display for ExecutionResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, step) in self.steps.iter().enumerate() {
+            if index > 0 {
+                f.write_str(" → ")?;
+            }
+            write!(f, "{}", step)?;
+        }
+        Ok(())
+    }
+}
+
+/// 명령 하나를 실행할 때의 제한과 환경입니다.
+#[derive(Debug, Clone)]
+pub struct RunOptions {
+    /// 최대 실행 시간. 초과하면 하위 프로세스까지 종료
+    pub timeout: Duration,
+    /// 보관할 표준 출력/오류의 최대 크기 (바이트)
+    pub max_output_bytes: usize,
+    /// 성공으로 볼 종료 코드 (비워 두면 0)
+    pub accepted_exit_codes: Vec<i32>,
+    /// 추가로 전달할 환경 변수
+    pub envs: Vec<(String, String)>,
+}
+
+impl RunOptions {
+    /// 복구 작업의 timeout_ms, max_output_bytes, accepted_exit_codes 설정
+    pub fn for_action(action: &RecoveryAction) -> Self {
+        Self {
+            timeout: action.get_timeout(),
+            max_output_bytes: action.get_max_output_bytes(),
+            accepted_exit_codes: action.accepted_exit_codes.clone(),
+            envs: Vec::new(),
+        }
+    }
+
+    fn is_accepted_exit_code(&self, code: i32) -> bool {
+        if self.accepted_exit_codes.is_empty() {
+            code == 0
+        } else {
+            self.accepted_exit_codes.contains(&code)
+        }
+    }
+}
+
+/// 셸을 거치지 않고 실행할 프로그램과 인수입니다.
+#[derive(Debug, Clone)]
+pub struct Invocation {
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+impl Invocation {
+    fn new(program: &str, args: &[&str]) -> Self {
+        Self {
+            program: program.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+        }
+    }
+
+    /// PowerShell 명령어로 실행합니다.
+    #[cfg(windows)]
+    fn powershell(command: &str) -> Self {
+        Self::shell(Shell::Powershell, command, &[])
+    }
+
+    /// 지정한 방식으로 명령어를 실행합니다. exec이면 셸 없이 `command`를 프로그램으로, `args`를 인수로 실행하고,
+    /// sh/bash이면 `args`를 위치 매개변수로 전달합니다. powershell/cmd는 `args`를 사용하지 않습니다.
+    pub fn shell(shell: Shell, command: &str, args: &[String]) -> Self {
+        let (program, mut arguments) = match shell {
+            Shell::Sh => ("sh", vec!["-c".to_string(), command.to_string(), "sh".to_string()]),
+            Shell::Bash => ("bash", vec!["-c".to_string(), command.to_string(), "bash".to_string()]),
+            Shell::Powershell => (POWERSHELL, vec!["-Command".to_string(), command.to_string()]),
+            Shell::Cmd => ("cmd", vec!["/C".to_string(), command.to_string()]),
+            Shell::Exec => (command, Vec::new()),
+        };
+        if shell.supports_args() {
+            arguments.extend(args.iter().cloned());
+        }
+        Self {
+            program: program.to_string(),
+            args: arguments,
+        }
+    }
+
+    /// 프로그램을 실행하고 결과를 반환합니다.
+    /// `options`의 timeout을 넘기면 하위 프로세스까지 종료하고, accepted_exit_codes로 성공 여부를 판단합니다.
+    pub async fn run(&self, options: &RunOptions) -> CommandResult {
+        let started = Instant::now();
+        let mut result = CommandResult {
+            command: self.to_string(),
+            exit_code: None,
+            duration: Duration::ZERO,
+            stdout: String::new(),
+            stderr: String::new(),
+            truncated: false,
+            timed_out: false,
+            error: None,
+            success: false,
+        };
+
+        let mut command = TokioCommand::new(&self.program);
+        command
+            .args(&self.args)
+            .envs(options.envs.iter().map(|(key, value)| (key, value)))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        // 시간 초과 시 하위 프로세스까지 한 번에 종료할 수 있도록 새 프로세스 그룹에서 실행
+        #[cfg(target_os = "linux")]
+        command.process_group(0);
+
+        let mut child = match command.spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                result.error = Some(format!("{} 실행 실패: {}", self.program, e));
+                result.duration = started.elapsed();
+                return result;
+            }
+        };
+
+        let limit = options.max_output_bytes;
+        let stdout = tokio::spawn(read_capped(child.stdout.take(), limit));
+        let stderr = tokio::spawn(read_capped(child.stderr.take(), limit));
+
+        match time::timeout(options.timeout, child.wait()).await {
+            Ok(Ok(status)) => result.exit_code = status.code(),
+            Ok(Err(e)) => result.error = Some(format!("{} 대기 실패: {}", self.program, e)),
+            Err(_) => {
+                kill_process_tree(&mut child).await;
+                result.timed_out = true;
+            }
+        }
+        result.duration = started.elapsed();
+
+        let (stdout, stdout_truncated) = collect_output(stdout).await;
+        let (stderr, stderr_truncated) = collect_output(stderr).await;
+        result.stdout = stdout;
+        result.stderr = stderr;
+        result.truncated = stdout_truncated || stderr_truncated;
+        result.success = !result.timed_out && result.exit_code.is_some_and(|code| options.is_accepted_exit_code(code));
+        result
+    }
+}
+
+impl fmt::Display for Invocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.program)?;
+        for arg in &self.args {
+            if arg.is_empty() || arg.contains(|c: char| c.is_whitespace() || "'\"$`\\;&|<>".contains(c)) {
+                write!(f, " {}", template::posix_quote(arg))?;
+            } else {
+                write!(f, " {}", arg)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 출력을 끝까지 읽되 `limit` 바이트까지만 보관합니다. 넘친 출력은 버리고 잘림 여부를 함께 반환합니다.
+async fn read_capped<R: AsyncRead + Unpin>(reader: Option<R>, limit: usize) -> (Vec<u8>, bool) {
+    let Some(mut reader) = reader else {
+        return (Vec::new(), false);
+    };
+    let mut kept = Vec::new();
+    let mut truncated = false;
+    let mut buffer = [0u8; 4096];
+    loop {
+        match reader.read(&mut buffer).await {
+            Ok(0) | Err(_) => break,
+            Ok(read) => {
+                let room = limit.saturating_sub(kept.len());
+                truncated |= read > room;
+                kept.extend_from_slice(&buffer[..read.min(room)]);
+            }
+        }
+    }
+    (kept, truncated)
+}
+
+/// 출력 읽기 작업의 결과를 가져옵니다. 제한 시간 안에 끝나지 않으면 출력 없이 잘린 것으로 처리합니다.
+async fn collect_output(reader: JoinHandle<(Vec<u8>, bool)>) -> (String, bool) {
+    let abort = reader.abort_handle();
+    match time::timeout(OUTPUT_DRAIN_TIMEOUT, reader).await {
+        Ok(Ok((bytes, truncated))) => (String::from_utf8_lossy(&bytes).to_string(), truncated),
+        _ => {
+            abort.abort();
+            (String::new(), true)
+        }
+    }
+}
+
+/// 프로세스와 같은 프로세스 그룹의 하위 프로세스를 모두 종료합니다.
+#[cfg(target_os = "linux")]
+async fn kill_process_tree(child: &mut Child) {
+    if let Some(pid) = child.id() {
+        unsafe {
+            libc::kill(-(pid as libc::pid_t), libc::SIGKILL);
+        }
+    }
+    let _ = child.kill().await;
+}
+
+/// 프로세스와 하위 프로세스를 모두 종료합니다.
+#[cfg(windows)]
+async fn kill_process_tree(child: &mut Child) {
+    if let Some(pid) = child.id() {
+        let _ = TokioCommand::new("taskkill")
+            .args(["/T", "/F", "/PID", &pid.to_string()])
+            .output()
+            .await;
+    }
+    let _ = child.kill().await;
+}
+
+/// 프로세스를 종료합니다.
+#[cfg(not(any(windows, target_os = "linux")))]
+async fn kill_process_tree(child: &mut Child) {
+    let _ = child.kill().await;
+}
+
+/// 복구 작업 하나를 실행하는 방법입니다.
+///
+/// 대안(alternative)을 순서대로 시도하며, 대안 안의 명령은 차례로 실행합니다.
+/// 명령이 하나라도 실패하면 다음 대안으로 넘어가고, 모든 대안이 실패하면 실패입니다.
+#[derive(Debug, Clone)]
+pub struct RecoveryPlan {
+    alternatives: Vec<Vec<Invocation>>,
+}
+
+impl RecoveryPlan {
+    fn single(invocations: Vec<Invocation>) -> Self {
+        Self {
+            alternatives: vec![invocations],
+        }
+    }
+
+    /// 복구 작업의 종류에 맞는 실행 방법을 만듭니다. command 종류는 작업 또는 전역 shell 설정으로 실행합니다.
+    /// 명령어와 매개변수의 템플릿 변수(`{target.name}` 등)는 `context`의 값으로 바꿉니다.
+    pub fn new(config: &Config, action: &RecoveryAction, context: &TemplateContext) -> Result<Self> {
+        let plan = match &action.kind {
+            RecoveryKind::RestartInterface { interface } => {
+                let interface = template::render_arg(interface, context);
+                if interface.trim().is_empty() {
+                    return Err(anyhow!("재시작할 인터페이스를 찾을 수 없음 (기본 경로 인터페이스 없음)"));
+                }
+                restart_interface(&interface)
+            }
+            RecoveryKind::FlushDns => flush_dns(),
+            RecoveryKind::RenewDhcp { interface } => {
+                renew_dhcp(interface.as_deref().map(|i| template::render_arg(i, context)).as_deref())
+            }
+            RecoveryKind::RestartService { service } => restart_service(&template::render_arg(service, context)),
+            RecoveryKind::Command { command, args } => {
+                let shell = config.get_action_shell(action);
+                let (command, args) = template::render_command(shell, command, args, context)?;
+                Self::single(vec![Invocation::shell(shell, &command, &args)])
+            }
+        };
+        Ok(plan)
+    }
+
+    /// 실행하고 실행한 명령마다 결과를 반환합니다.
+    pub async fn run(&self, action: &RecoveryAction) -> ExecutionResult {
+        let options = RunOptions::for_action(action);
+        let mut steps = Vec::new();
+        'alternatives: for invocations in &self.alternatives {
+            for invocation in invocations {
+                let result = invocation.run(&options).await;
+                let success = result.success;
+                steps.push(result);
+                if !success {
+                    continue 'alternatives;
+                }
+            }
+            return ExecutionResult { steps, success: true };
+        }
+        ExecutionResult { steps, success: false }
+    }
+}
+
+impl fmt::Display for RecoveryPlan {
+    /// 셸 표기(`&&`, `||`)로 실행할 명령을 보여 줍니다.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, invocations) in self.alternatives.iter().enumerate() {
+            if index > 0 {
+                f.write_str(" || ")?;
+            }
+            for (step, invocation) in invocations.iter().enumerate() {
+                if step > 0 {
+                    f.write_str(" && ")?;
+                }
+                write!(f, "{}", invocation)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 네트워크 인터페이스를 재시작합니다.
+#[cfg(windows)]
+fn restart_interface(interface: &str) -> RecoveryPlan {
+    let command = format!("Restart-NetAdapter -Name {} -Confirm:$false", template::powershell_quote(interface));
+    RecoveryPlan::single(vec![Invocation::powershell(&command)])
+}
+
+/// 네트워크 인터페이스를 재시작합니다. (Linux: `ip link set down/up`)
+#[cfg(not(windows))]
+fn restart_interface(interface: &str) -> RecoveryPlan {
+    RecoveryPlan::single(vec![
+        Invocation::new("ip", &["link", "set", "dev", interface, "down"]),
+        Invocation::new("ip", &["link", "set", "dev", interface, "up"]),
+    ])
+}
+
+/// DNS 캐시를 초기화합니다.
+#[cfg(windows)]
+fn flush_dns() -> RecoveryPlan {
+    RecoveryPlan::single(vec![Invocation::powershell("Clear-DnsClientCache")])
+}
+
+/// DNS 캐시를 초기화합니다. (Linux: systemd-resolved, resolvectl이 없는 이전 버전은 systemd-resolve)
+#[cfg(not(windows))]
+fn flush_dns() -> RecoveryPlan {
+    RecoveryPlan {
+        alternatives: vec![
+            vec![Invocation::new("resolvectl", &["flush-caches"])],
+            vec![Invocation::new("systemd-resolve", &["--flush-caches"])],
+        ],
+    }
+}
+
+/// DHCP로 IP 설정을 갱신합니다. 인터페이스를 지정하지 않으면 모든 인터페이스를 갱신합니다.
+#[cfg(windows)]
+fn renew_dhcp(interface: Option<&str>) -> RecoveryPlan {
+    let command = match interface {
+        Some(interface) => format!("ipconfig /renew {}", template::powershell_quote(interface)),
+        None => "ipconfig /renew".to_string(),
+    };
+    RecoveryPlan::single(vec![Invocation::powershell(&command)])
+}
+
+/// DHCP로 IP 설정을 갱신합니다. 인터페이스를 지정하지 않으면 모든 인터페이스를 갱신합니다.
+/// (Linux: systemd-networkd가 관리하는 인터페이스는 `networkctl renew`, 그 외에는 `dhclient`)
+#[cfg(not(windows))]
+fn renew_dhcp(interface: Option<&str>) -> RecoveryPlan {
+    let interfaces: Vec<&str> = interface.into_iter().collect();
+    let release: Vec<&str> = std::iter::once("-r").chain(interfaces.iter().copied()).collect();
+    let dhclient = vec![Invocation::new("dhclient", &release), Invocation::new("dhclient", &interfaces)];
+
+    let mut alternatives = Vec::new();
+    if let Some(interface) = interface {
+        alternatives.push(vec![Invocation::new("networkctl", &["renew", interface])]);
+    }
+    alternatives.push(dhclient);
+    RecoveryPlan { alternatives }
+}
+
+/// 시스템 서비스를 재시작합니다.
+#[cfg(windows)]
+fn restart_service(service: &str) -> RecoveryPlan {
+    let command = format!("Restart-Service -Name {} -Force", template::powershell_quote(service));
+    RecoveryPlan::single(vec![Invocation::powershell(&command)])
+}
+
+/// 시스템 서비스를 재시작합니다. (Linux: systemd)
+#[cfg(not(windows))]
+fn restart_service(service: &str) -> RecoveryPlan {
+    RecoveryPlan::single(vec![Invocation::new("systemctl", &["restart", service])])
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn options(timeout: Duration) -> RunOptions {
+        RunOptions {
+            timeout,
+            max_output_bytes: 8,
+            accepted_exit_codes: Vec::new(),
+            envs: vec![("NETWORK_MONITOR_STATE".to_string(), "down".to_string())],
+        }
+    }
+
+    #[tokio::test]
+    async fn passes_envs_and_caps_output() {
+        let invocation = Invocation::shell(Shell::Sh, "printf '%s-0123456789' \"$NETWORK_MONITOR_STATE\"", &[]);
+        let result = invocation.run(&options(Duration::from_secs(5))).await;
+        assert!(result.success, "{}", result);
+        assert_eq!(result.stdout, "down-012");
+        assert!(result.truncated);
+    }
+
+    #[tokio::test]
+    async fn kills_commands_that_outlive_timeout() {
+        let invocation = Invocation::shell(Shell::Sh, "sleep 30", &[]);
+        let result = invocation.run(&options(Duration::from_millis(100))).await;
+        assert!(result.timed_out);
+        assert!(!result.success);
+        assert!(result.duration < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn uses_accepted_exit_codes() {
+        let mut options = options(Duration::from_secs(5));
+        let invocation = Invocation::shell(Shell::Sh, "exit 3", &[]);
+        assert!(!invocation.run(&options).await.success);
+        options.accepted_exit_codes = vec![0, 3];
+        let result = invocation.run(&options).await;
+        assert!(result.success);
+        assert_eq!(result.exit_code, Some(3));
+    }
+}
@@ -0,0 +1,140 @@
+use std::fmt;
+use std::time::Duration;
+
+/// 여러 번의 ICMP 에코 결과를 요약한 통계입니다.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PingStats {
+    pub transmitted: u32,
+    pub received: u32,
+    pub loss_percent: f64,
+    pub min: Duration,
+    pub avg: Duration,
+    pub max: Duration,
+    /// RTT 표준 편차 (iputils `ping`의 mdev와 동일한 방식)
+    pub mdev: Duration,
+    /// 연속된 응답 간 RTT 차이의 평균
+    pub jitter: Duration,
+}
+
+impl PingStats {
+    /// 전송한 패킷 수와 수신된 응답들의 RTT(전송 순서)로 통계를 계산합니다.
+    pub fn from_samples(transmitted: u32, samples: &[Duration]) -> Self {
+        let received = samples.len() as u32;
+        let loss_percent = if transmitted == 0 {
+            0.0
+        } else {
+            f64::from(transmitted.saturating_sub(received)) * 100.0 / f64::from(transmitted)
+        };
+
+        if samples.is_empty() {
+            return Self {
+                transmitted,
+                received,
+                loss_percent,
+                ..Default::default()
+            };
+        }
+
+        let secs: Vec<f64> = samples.iter().map(Duration::as_secs_f64).collect();
+        let count = secs.len() as f64;
+        let mean = secs.iter().sum::<f64>() / count;
+        let mean_sq = secs.iter().map(|s| s * s).sum::<f64>() / count;
+        let mdev = (mean_sq - mean * mean).max(0.0).sqrt();
+        let jitter = if secs.len() > 1 {
+            secs.windows(2).map(|w| (w[1] - w[0]).abs()).sum::<f64>() / (count - 1.0)
+        } else {
+            0.0
+        };
+
+        Self {
+            transmitted,
+            received,
+            loss_percent,
+            min: samples.iter().min().copied().unwrap_or_default(),
+            avg: Duration::from_secs_f64(mean),
+            max: samples.iter().max().copied().unwrap_or_default(),
+            mdev: Duration::from_secs_f64(mdev),
+            jitter: Duration::from_secs_f64(jitter),
+        }
+    }
+}
+
+impl fmt::Display for PingStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{} 수신, 손실 {:.1}%, rtt min/avg/max/mdev = {:.2}/{:.2}/{:.2}/{:.2} ms, jitter {:.2} ms",
+            self.received,
+            self.transmitted,
+            self.loss_percent,
+            as_millis_f64(self.min),
+            as_millis_f64(self.avg),
+            as_millis_f64(self.max),
+            as_millis_f64(self.mdev),
+            as_millis_f64(self.jitter),
+        )
+    }
+}
+
+/// Duration을 소수점 밀리초로 변환합니다.
+pub fn as_millis_f64(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    fn assert_close(actual: Duration, expected_ms: f64) {
+        assert!(
+            (as_millis_f64(actual) - expected_ms).abs() < 0.001,
+            "{:.4} ms != {:.4} ms",
+            as_millis_f64(actual),
+            expected_ms
+        );
+    }
+
+    #[test]
+    fn matches_iputils_summary() {
+        // 5개 전송, 4개 수신: ping -c 5 요약 기준
+        // rtt min/avg/max/mdev = 10/25/40/11.180, 손실 20%
+        let stats = PingStats::from_samples(5, &[ms(10), ms(30), ms(20), ms(40)]);
+        assert_eq!(stats.transmitted, 5);
+        assert_eq!(stats.received, 4);
+        assert_eq!(stats.loss_percent, 20.0);
+        assert_eq!(stats.min, ms(10));
+        assert_eq!(stats.max, ms(40));
+        assert_close(stats.avg, 25.0);
+        assert_close(stats.mdev, 125f64.sqrt());
+        // |30-10| + |20-30| + |40-20| = 50, 3개 구간 평균
+        assert_close(stats.jitter, 50.0 / 3.0);
+    }
+
+    #[test]
+    fn single_reply_has_no_deviation() {
+        let stats = PingStats::from_samples(1, &[ms(7)]);
+        assert_eq!(stats.loss_percent, 0.0);
+        assert_eq!((stats.min, stats.avg, stats.max), (ms(7), ms(7), ms(7)));
+        assert_eq!(stats.mdev, Duration::ZERO);
+        assert_eq!(stats.jitter, Duration::ZERO);
+    }
+
+    #[test]
+    fn no_reply_is_total_loss() {
+        let stats = PingStats::from_samples(3, &[]);
+        assert_eq!(stats.received, 0);
+        assert_eq!(stats.loss_percent, 100.0);
+        assert_eq!(stats.avg, Duration::ZERO);
+        assert_eq!(stats.mdev, Duration::ZERO);
+        assert_eq!(stats.jitter, Duration::ZERO);
+    }
+
+    #[test]
+    fn zero_transmitted_reports_no_loss() {
+        assert_eq!(PingStats::from_samples(0, &[]), PingStats::default());
+    }
+}
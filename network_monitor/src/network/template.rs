@@ -0,0 +1,338 @@
+use crate::config::Shell;
+use anyhow::{anyhow, Result};
+use std::time::Duration;
+
+/// 명령 템플릿에 넣을 값입니다. 값이 없는 변수는 빈 문자열로 바뀝니다.
+#[derive(Debug, Clone, Default)]
+pub struct TemplateContext {
+    /// `{target.name}`: 장애 대상 (복구 작업은 근본 원인 중 첫 번째 대상)
+    pub target_name: Option<String>,
+    /// `{target.address}`
+    pub target_address: Option<String>,
+    /// `{interface}`: 기본 경로가 사용하는 인터페이스
+    pub interface: Option<String>,
+    /// `{failure_duration}`: 장애 시간 (초)
+    pub failure_duration: Option<Duration>,
+    /// `{event}`: 알림은 상태(down, up, flapping, recovered, gave_up), 복구 작업은 outage 또는 manual
+    pub event: Option<String>,
+}
+
+impl TemplateContext {
+    /// 변수 값. 알 수 없는 변수 이름이면 None
+    fn value(&self, name: &str) -> Option<String> {
+        let value = match name {
+            "target.name" => self.target_name.clone(),
+            "target.address" => self.target_address.clone(),
+            "interface" => self.interface.clone(),
+            "failure_duration" => self.failure_duration.map(|d| d.as_secs().to_string()),
+            "event" => self.event.clone(),
+            _ => return None,
+        };
+        Some(value.unwrap_or_default())
+    }
+}
+
+/// 템플릿의 변수를 값으로 바꿉니다. 값은 `quote`로 감싸서 넣습니다.
+/// 알려진 변수만 바꾸므로 PowerShell 스크립트 블록 같은 다른 중괄호는 그대로 남습니다.
+fn render_with(template: &str, context: &TemplateContext, quote: impl Fn(&str) -> Result<String>) -> Result<String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let candidate = &rest[start + 1..];
+        let variable = candidate
+            .find('}')
+            .and_then(|end| context.value(&candidate[..end]).map(|value| (end, value)));
+        match variable {
+            Some((end, value)) => {
+                rendered.push_str(&quote(&value)?);
+                rest = &candidate[end + 1..];
+            }
+            None => {
+                rendered.push('{');
+                rest = candidate;
+            }
+        }
+    }
+    rendered.push_str(rest);
+    Ok(rendered)
+}
+
+/// 셸을 거치지 않는 인수(argv 하나)로 쓰일 템플릿의 변수를 값 그대로 바꿉니다.
+pub fn render_arg(template: &str, context: &TemplateContext) -> String {
+    render_with(template, context, |value| Ok(value.to_string())).unwrap_or_else(|_| template.to_string())
+}
+
+/// 명령어와 인수의 변수를 값으로 바꿉니다.
+/// 셸이 해석하는 명령어에는 셸에 맞게 따옴표로 감싼 값을 넣고, exec의 프로그램과 인수 및 sh/bash의 위치 매개변수에는 값을 그대로 넣습니다.
+/// 감싼 값은 따옴표 밖(단어 위치)에서만 안전하므로, 셸 명령어에서 변수가 따옴표 안에 있으면 거부합니다.
+pub fn render_command(
+    shell: Shell,
+    command: &str,
+    args: &[String],
+    context: &TemplateContext,
+) -> Result<(String, Vec<String>)> {
+    let command = match shell {
+        Shell::Exec => render_arg(command, context),
+        _ => {
+            ensure_unquoted(shell, command, context)?;
+            render_with(command, context, |value| quote(shell, value))?
+        }
+    };
+    let args = args.iter().map(|arg| render_arg(arg, context)).collect();
+    Ok((command, args))
+}
+
+/// 셸 명령어 템플릿에서 따옴표 안에 쓴 변수가 없는지 미리 검사합니다 (설정 검증용).
+pub fn check_command(shell: Shell, command: &str) -> Result<()> {
+    match shell {
+        Shell::Exec => Ok(()),
+        _ => ensure_unquoted(shell, command, &TemplateContext::default()),
+    }
+}
+
+/// 템플릿 변수가 사용자가 쓴 따옴표 안에 있으면 오류를 반환합니다.
+/// 예를 들어 sh의 `echo "{target.name}"`은 큰따옴표 안에 작은따옴표 문자열이 들어가 `$(...)`가 다시 해석됩니다.
+fn ensure_unquoted(shell: Shell, template: &str, context: &TemplateContext) -> Result<()> {
+    for (start, _) in template.match_indices('{') {
+        let candidate = &template[start + 1..];
+        let Some(name) = candidate.find('}').map(|end| &candidate[..end]) else {
+            continue;
+        };
+        if context.value(name).is_some() && is_quoted(shell, &template[..start]) {
+            return Err(anyhow!(
+                "템플릿 변수 {{{}}}는 따옴표 밖에 써야 합니다 (값은 자동으로 따옴표 처리됨): {}",
+                name,
+                template
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// `prefix`가 끝나는 위치가 따옴표 문자열 안인지 확인합니다.
+fn is_quoted(shell: Shell, prefix: &str) -> bool {
+    #[derive(PartialEq)]
+    enum Span {
+        Bare,
+        Single,
+        Double,
+    }
+
+    let mut span = Span::Bare;
+    let mut chars = prefix.chars().peekable();
+    while let Some(c) = chars.next() {
+        match shell {
+            Shell::Sh | Shell::Bash => match (&span, c) {
+                (Span::Bare | Span::Double, '\\') => {
+                    chars.next();
+                }
+                (Span::Bare, '\'') => span = Span::Single,
+                (Span::Bare, '"') => span = Span::Double,
+                (Span::Single, '\'') | (Span::Double, '"') => span = Span::Bare,
+                _ => {}
+            },
+            Shell::Powershell => match (&span, c) {
+                (Span::Bare | Span::Double, '`') => {
+                    chars.next();
+                }
+                (Span::Bare, c) if is_powershell_single_quote(c) => span = Span::Single,
+                (Span::Bare, c) if is_powershell_double_quote(c) => span = Span::Double,
+                // 작은따옴표 문자열 안에서 연속된 작은따옴표 두 개는 글자 그대로의 작은따옴표
+                (Span::Single, c) if is_powershell_single_quote(c) => {
+                    if chars.peek().copied().is_some_and(is_powershell_single_quote) {
+                        chars.next();
+                    } else {
+                        span = Span::Bare;
+                    }
+                }
+                (Span::Double, c) if is_powershell_double_quote(c) => {
+                    if chars.peek().copied().is_some_and(is_powershell_double_quote) {
+                        chars.next();
+                    } else {
+                        span = Span::Bare;
+                    }
+                }
+                _ => {}
+            },
+            Shell::Cmd => {
+                if c == '"' {
+                    span = if span == Span::Bare { Span::Double } else { Span::Bare };
+                }
+            }
+            Shell::Exec => {}
+        }
+    }
+    span != Span::Bare
+}
+
+/// PowerShell이 작은따옴표로 취급하는 문자 (', ‘, ’, ‚, ‛)
+fn is_powershell_single_quote(c: char) -> bool {
+    matches!(c, '\'' | '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}')
+}
+
+/// PowerShell이 큰따옴표로 취급하는 문자 (", “, ”, „)
+fn is_powershell_double_quote(c: char) -> bool {
+    matches!(c, '"' | '\u{201C}' | '\u{201D}' | '\u{201E}')
+}
+
+/// 값을 셸 명령어 안에서 글자 그대로 해석되도록 감쌉니다.
+pub fn quote(shell: Shell, value: &str) -> Result<String> {
+    match shell {
+        Shell::Sh | Shell::Bash => Ok(posix_quote(value)),
+        Shell::Powershell => Ok(powershell_quote(value)),
+        Shell::Cmd => cmd_quote(value),
+        Shell::Exec => Ok(value.to_string()),
+    }
+}
+
+/// POSIX 셸 작은따옴표 문자열로 감쌉니다.
+pub fn posix_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// PowerShell 작은따옴표 문자열로 감쌉니다.
+/// PowerShell은 ‘ ’ ‚ ‛도 작은따옴표로 취급하므로 모두 두 번 써서 이스케이프합니다.
+pub fn powershell_quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('\'');
+    for c in value.chars() {
+        if is_powershell_single_quote(c) {
+            quoted.push(c);
+        }
+        quoted.push(c);
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// cmd 큰따옴표 문자열로 감쌉니다. cmd는 따옴표 안에서도 `%`를 확장하고 `"`를 이스케이프할 수 없으므로
+/// 이런 문자가 들어 있는 값은 거부합니다.
+fn cmd_quote(value: &str) -> Result<String> {
+    if value.contains(['"', '%', '!', '\r', '\n']) {
+        return Err(anyhow!("cmd 명령어에 넣을 수 없는 문자가 포함된 값: {}", value));
+    }
+    Ok(format!("\"{}\"", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 셸 특수 문자가 들어 있는 값들
+    const HOSTILE: [&str; 7] = [
+        "it's",
+        "say \"hi\"",
+        "$(touch /tmp/pwned)",
+        "`id`",
+        "100%",
+        "wow!",
+        "line1\nline2",
+    ];
+
+    fn context(name: &str) -> TemplateContext {
+        TemplateContext {
+            target_name: Some(name.to_string()),
+            target_address: Some("192.0.2.1".to_string()),
+            interface: None,
+            failure_duration: Some(Duration::from_secs(90)),
+            event: Some("down".to_string()),
+        }
+    }
+
+    #[test]
+    fn posix_quote_escapes_single_quotes() {
+        assert_eq!(posix_quote("plain"), "'plain'");
+        assert_eq!(posix_quote("it's"), r"'it'\''s'");
+        assert_eq!(posix_quote("$(id) `id` \"x\""), "'$(id) `id` \"x\"'");
+        assert_eq!(posix_quote(""), "''");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn posix_quote_round_trips_through_sh() {
+        for value in HOSTILE {
+            let output = std::process::Command::new("sh")
+                .args(["-c", &format!("printf %s {}", posix_quote(value))])
+                .output()
+                .unwrap();
+            assert_eq!(String::from_utf8_lossy(&output.stdout), value);
+        }
+    }
+
+    #[test]
+    fn powershell_quote_doubles_every_single_quote_form() {
+        assert_eq!(powershell_quote("it's"), "'it''s'");
+        assert_eq!(powershell_quote("a\u{2018}b\u{2019}c"), "'a\u{2018}\u{2018}b\u{2019}\u{2019}c'");
+        assert_eq!(powershell_quote("\u{201A}\u{201B}"), "'\u{201A}\u{201A}\u{201B}\u{201B}'");
+        // 작은따옴표 문자열 안에서는 $, `, " 가 해석되지 않음
+        assert_eq!(powershell_quote("$(id) `n \"x\""), "'$(id) `n \"x\"'");
+    }
+
+    #[test]
+    fn cmd_quote_rejects_unquotable_values() {
+        assert_eq!(cmd_quote("Local Router").unwrap(), "\"Local Router\"");
+        assert_eq!(cmd_quote("a & b | c").unwrap(), "\"a & b | c\"");
+        for value in ["say \"hi\"", "100%", "wow!", "line1\nline2", "cr\r"] {
+            assert!(cmd_quote(value).is_err(), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn render_with_replaces_known_variables_only() {
+        let rendered = render_with(
+            "{target.name} {target.address} {interface} {failure_duration} {event} {unknown} {target.name",
+            &context("a"),
+            |value| Ok(format!("<{}>", value)),
+        )
+        .unwrap();
+        assert_eq!(rendered, "<a> <192.0.2.1> <> <90> <down> {unknown} {target.name");
+        // PowerShell 스크립트 블록 같은 다른 중괄호는 그대로 유지
+        assert_eq!(render_arg("{ $_ } {{event}}", &context("a")), "{ $_ } {down}");
+    }
+
+    #[test]
+    fn render_command_quotes_values_for_each_shell() {
+        for value in HOSTILE {
+            let ctx = context(value);
+            let (sh, _) = render_command(Shell::Sh, "echo {target.name}", &[], &ctx).unwrap();
+            assert_eq!(sh, format!("echo {}", posix_quote(value)));
+            let (ps, _) = render_command(Shell::Powershell, "Write-Output {target.name}", &[], &ctx).unwrap();
+            assert_eq!(ps, format!("Write-Output {}", powershell_quote(value)));
+            assert_eq!(render_command(Shell::Cmd, "echo {target.name}", &[], &ctx).is_ok(), cmd_quote(value).is_ok());
+        }
+    }
+
+    #[test]
+    fn render_command_passes_exec_and_args_verbatim() {
+        let ctx = context("$(id)");
+        let args = vec!["--name={target.name}".to_string()];
+        let (command, args) = render_command(Shell::Exec, "notify {target.name}", &args, &ctx).unwrap();
+        assert_eq!(command, "notify $(id)");
+        assert_eq!(args, vec!["--name=$(id)".to_string()]);
+    }
+
+    #[test]
+    fn render_command_rejects_variables_inside_quotes() {
+        let ctx = context("$(id)");
+        assert!(render_command(Shell::Sh, "echo \"{target.name}\"", &[], &ctx).is_err());
+        assert!(render_command(Shell::Bash, "echo 'x {event}'", &[], &ctx).is_err());
+        assert!(render_command(Shell::Powershell, "Write-Output \"{target.name}\"", &[], &ctx).is_err());
+        assert!(render_command(Shell::Powershell, "Write-Output \u{2018}{target.name}\u{2019}", &[], &ctx).is_err());
+        assert!(render_command(Shell::Cmd, "echo \"{event}\"", &[], &ctx).is_err());
+
+        // 따옴표가 닫힌 뒤, 이스케이프된 따옴표 뒤, 알 수 없는 변수는 허용
+        assert!(render_command(Shell::Sh, "echo \"state:\" {event} '{unknown}'", &[], &ctx).is_ok());
+        assert!(render_command(Shell::Sh, r#"echo \"{event}"#, &[], &ctx).is_ok());
+        assert!(render_command(Shell::Powershell, "Write-Output 'it''s' {event}", &[], &ctx).is_ok());
+        assert!(render_command(Shell::Powershell, "Write-Output `\"{event}", &[], &ctx).is_ok());
+        assert!(render_command(Shell::Exec, "echo \"{event}\"", &[], &ctx).is_ok());
+    }
+
+    #[test]
+    fn check_command_validates_without_values() {
+        assert!(check_command(Shell::Sh, "logger -t network_monitor -- {target.name} {event}").is_ok());
+        assert!(check_command(Shell::Sh, "logger \"{target.name}\"").is_err());
+        assert!(check_command(Shell::Exec, "logger \"{target.name}\"").is_ok());
+    }
+}
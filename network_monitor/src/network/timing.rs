@@ -0,0 +1,117 @@
+use super::stats::as_millis_f64;
+use anyhow::{anyhow, Result};
+use native_tls::TlsConnector;
+use std::fmt;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use tokio::net::{lookup_host, TcpStream};
+use tokio::time::timeout;
+use tokio_native_tls::TlsStream;
+
+/// 연결 단계별 소요 시간입니다. 각 값은 이전 단계가 끝난 시점부터 측정합니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectionTiming {
+    /// 실제로 연결된 주소
+    pub peer: SocketAddr,
+    /// 호스트 이름 해석
+    pub resolve: Duration,
+    /// TCP 핸드셰이크
+    pub connect: Duration,
+    /// TLS 핸드셰이크 (TLS를 사용한 경우)
+    pub tls: Option<Duration>,
+    /// 요청 전송 후 첫 응답 바이트까지 (요청을 보낸 경우)
+    pub first_byte: Option<Duration>,
+}
+
+impl ConnectionTiming {
+    /// 모든 단계의 합계
+    pub fn total(&self) -> Duration {
+        self.resolve + self.connect + self.tls.unwrap_or_default() + self.first_byte.unwrap_or_default()
+    }
+}
+
+impl fmt::Display for ConnectionTiming {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "DNS {:.2} ms, TCP {:.2} ms",
+            as_millis_f64(self.resolve),
+            as_millis_f64(self.connect)
+        )?;
+        if let Some(tls) = self.tls {
+            write!(f, ", TLS {:.2} ms", as_millis_f64(tls))?;
+        }
+        if let Some(first_byte) = self.first_byte {
+            write!(f, ", 첫 바이트 {:.2} ms", as_millis_f64(first_byte))?;
+        }
+        Ok(())
+    }
+}
+
+/// 호스트 이름을 해석하고 TCP 연결을 맺으며 단계별 시간을 측정합니다.
+/// 해석된 주소를 순서대로 시도하고, 전체 시간이 `timeout_duration`을 넘으면 실패합니다.
+pub async fn connect(host: &str, port: u16, timeout_duration: Duration) -> Result<(TcpStream, ConnectionTiming)> {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let start = Instant::now();
+
+    let addrs: Vec<SocketAddr> = timeout(timeout_duration, lookup_host((host, port)))
+        .await
+        .map_err(|_| anyhow!("호스트 이름 해석 시간 초과 ({})", host))?
+        .map_err(|e| anyhow!("호스트 이름 해석 실패 ({}): {}", host, e))?
+        .collect();
+    let resolve = start.elapsed();
+
+    let mut last_error = anyhow!("호스트 '{}'의 주소를 찾을 수 없음", host);
+    for addr in addrs {
+        let connect_start = Instant::now();
+        match timeout(timeout_duration.saturating_sub(start.elapsed()), TcpStream::connect(addr)).await {
+            Ok(Ok(stream)) => {
+                let timing = ConnectionTiming {
+                    peer: addr,
+                    resolve,
+                    connect: connect_start.elapsed(),
+                    tls: None,
+                    first_byte: None,
+                };
+                return Ok((stream, timing));
+            }
+            Ok(Err(e)) => last_error = anyhow!("포트 연결 실패: {}", e),
+            Err(_) => return Err(anyhow!("포트 연결 시간 초과")),
+        }
+    }
+    Err(last_error)
+}
+
+/// TCP 연결 후 TLS 핸드셰이크까지 수행하며 단계별 시간을 측정합니다.
+/// `verify`가 false이면 체인과 호스트 이름 검증을 건너뜁니다.
+/// 핸드셰이크 자체가 실패한 경우 오류는 `native_tls::Error`로 downcast할 수 있습니다.
+pub async fn connect_tls(
+    host: &str,
+    port: u16,
+    server_name: &str,
+    verify: bool,
+    timeout_duration: Duration,
+) -> Result<(TlsStream<TcpStream>, ConnectionTiming)> {
+    let start = Instant::now();
+    let (stream, mut timing) = connect(host, port, timeout_duration).await?;
+
+    let connector = TlsConnector::builder()
+        .danger_accept_invalid_certs(!verify)
+        .danger_accept_invalid_hostnames(!verify)
+        .build()
+        .map_err(|e| anyhow!("TLS 커넥터 생성 실패: {}", e))?;
+    let connector = tokio_native_tls::TlsConnector::from(connector);
+
+    let tls_start = Instant::now();
+    let tls_stream = timeout(
+        timeout_duration.saturating_sub(start.elapsed()),
+        connector.connect(server_name, stream),
+    )
+    .await
+    .map_err(|_| anyhow!("TLS 핸드셰이크 시간 초과"))?
+    // 호출자가 인증서 검증 실패를 구분할 수 있도록 `native_tls::Error`를 그대로 보존
+    .map_err(anyhow::Error::new)?;
+    timing.tls = Some(tls_start.elapsed());
+
+    Ok((tls_stream, timing))
+}
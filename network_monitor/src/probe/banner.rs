@@ -0,0 +1,213 @@
+use super::{ProbeDetail, ProbeReport};
+use crate::config::{BannerProbe, Config, NetworkTarget, TransportProtocol};
+use crate::network::timing::{self, ConnectionTiming};
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{lookup_host, UdpSocket};
+use tokio::time::timeout;
+
+/// 응답으로 읽을 최대 바이트 수
+const MAX_RESPONSE_BYTES: usize = 4096;
+
+/// 응답 조건입니다.
+struct Expectation {
+    prefix: Option<Vec<u8>>,
+    regex: Option<Regex>,
+}
+
+impl Expectation {
+    fn from_probe(probe: &BannerProbe) -> Result<Self> {
+        let prefix = match (&probe.expect_prefix, &probe.expect_prefix_hex) {
+            (Some(text), _) => Some(text.as_bytes().to_vec()),
+            (None, Some(hex)) => Some(decode_hex(hex)?),
+            (None, None) => None,
+        };
+        let regex = probe
+            .expect_regex
+            .as_deref()
+            .map(Regex::new)
+            .transpose()
+            .map_err(|e| anyhow!("잘못된 응답 정규식: {}", e))?;
+        Ok(Self { prefix, regex })
+    }
+
+    /// 지금까지 받은 응답이 조건을 만족하는지 확인합니다.
+    /// 조건이 없으면 응답이 하나라도 있으면 만족합니다.
+    fn is_satisfied(&self, response: &[u8]) -> bool {
+        if response.is_empty() {
+            return false;
+        }
+        let prefix_ok = self
+            .prefix
+            .as_ref()
+            .is_none_or(|prefix| response.starts_with(prefix));
+        let regex_ok = self
+            .regex
+            .as_ref()
+            .is_none_or(|regex| regex.is_match(&String::from_utf8_lossy(response)));
+        prefix_ok && regex_ok
+    }
+}
+
+/// 16진수 문자열(공백 허용)을 바이트로 변환합니다.
+fn decode_hex(hex: &str) -> Result<Vec<u8>> {
+    let digits: Vec<u8> = hex.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    if !digits.iter().all(u8::is_ascii_hexdigit) {
+        return Err(anyhow!("16진수가 아닌 문자가 포함되어 있습니다: {}", hex));
+    }
+    if digits.len() % 2 != 0 {
+        return Err(anyhow!("16진수 데이터의 길이가 홀수입니다: {}", hex));
+    }
+    // 모두 ASCII 16진수임을 확인했으므로 바이트 단위로 안전하게 나눌 수 있습니다.
+    let nibble = |b: u8| (b as char).to_digit(16).unwrap_or(0) as u8;
+    Ok(digits.chunks(2).map(|pair| nibble(pair[0]) << 4 | nibble(pair[1])).collect())
+}
+
+fn payload(probe: &BannerProbe) -> Result<Vec<u8>> {
+    match (&probe.send, &probe.send_hex) {
+        (Some(text), _) => Ok(text.as_bytes().to_vec()),
+        (None, Some(hex)) => decode_hex(hex),
+        (None, None) => Ok(Vec::new()),
+    }
+}
+
+/// 로그에 표시할 수 있도록 응답을 한 줄 텍스트로 요약합니다.
+fn preview(response: &[u8]) -> String {
+    let text = String::from_utf8_lossy(&response[..response.len().min(80)]);
+    text.escape_debug().to_string()
+}
+
+/// TCP로 연결해 데이터를 보내고, 조건을 만족하거나 연결이 닫힐 때까지 응답을 읽습니다.
+/// 첫 바이트 시간은 연결(또는 데이터 전송) 직후부터 측정합니다.
+async fn exchange_tcp(
+    host: &str,
+    port: u16,
+    payload: &[u8],
+    expectation: &Expectation,
+    timeout_duration: Duration,
+) -> Result<(Vec<u8>, Option<ConnectionTiming>)> {
+    let start = Instant::now();
+    let (mut stream, mut timing) = timing::connect(host, port, timeout_duration).await?;
+    if !payload.is_empty() {
+        stream.write_all(payload).await?;
+    }
+    let request_start = Instant::now();
+
+    let mut response = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !expectation.is_satisfied(&response) && response.len() < MAX_RESPONSE_BYTES {
+        let remaining = timeout_duration.saturating_sub(start.elapsed());
+        match timeout(remaining, stream.read(&mut buf)).await {
+            Ok(Ok(0)) => break,
+            Ok(Ok(len)) => {
+                if response.is_empty() {
+                    timing.first_byte = Some(request_start.elapsed());
+                }
+                response.extend_from_slice(&buf[..len]);
+            }
+            Ok(Err(e)) => return Err(anyhow!("응답 읽기 실패: {}", e)),
+            // 시간 초과 시 지금까지 받은 응답으로 판단
+            Err(_) => break,
+        }
+    }
+    Ok((response, Some(timing)))
+}
+
+/// UDP 데이터그램을 보내고 첫 번째 응답을 받습니다.
+async fn exchange_udp(host: &str, port: u16, payload: &[u8], timeout_duration: Duration) -> Result<Vec<u8>> {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let server = lookup_host((host, port))
+        .await
+        .map_err(|e| anyhow!("호스트 이름 해석 실패 ({}): {}", host, e))?
+        .next()
+        .ok_or_else(|| anyhow!("호스트 '{}'의 주소를 찾을 수 없음", host))?;
+
+    let bind_addr = if server.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+    let socket = UdpSocket::bind(bind_addr).await?;
+    socket.connect(server).await?;
+    socket.send(payload).await?;
+
+    let mut buf = vec![0u8; MAX_RESPONSE_BYTES];
+    let len = timeout(timeout_duration, socket.recv(&mut buf))
+        .await
+        .map_err(|_| anyhow!("UDP 응답 시간 초과"))?
+        .map_err(|e| anyhow!("UDP 응답 수신 실패: {}", e))?;
+    buf.truncate(len);
+    Ok(buf)
+}
+
+/// 설정된 데이터를 TCP/UDP로 보내고 응답이 접두사/정규식 조건과 일치하는지 확인합니다.
+pub async fn run(config: &Config, target: &NetworkTarget, probe: &BannerProbe) -> ProbeReport {
+    let port = match probe.port.or(target.port) {
+        Some(port) => port,
+        None => return ProbeReport::error("배너 확인에는 port 설정이 필요합니다"),
+    };
+    let payload = match payload(probe) {
+        Ok(payload) => payload,
+        Err(e) => return ProbeReport::error(e),
+    };
+    let expectation = match Expectation::from_probe(probe) {
+        Ok(expectation) => expectation,
+        Err(e) => return ProbeReport::error(e),
+    };
+
+    let timeout_duration = config.get_target_timeout(target);
+    let start = Instant::now();
+    let (protocol, result) = match probe.protocol {
+        TransportProtocol::Tcp => (
+            "tcp",
+            exchange_tcp(&target.address, port, &payload, &expectation, timeout_duration).await,
+        ),
+        TransportProtocol::Udp => (
+            "udp",
+            exchange_udp(&target.address, port, &payload, timeout_duration)
+                .await
+                .map(|response| (response, None)),
+        ),
+    };
+    let latency = start.elapsed();
+    let endpoint = format!("{}/{}:{}", protocol, target.address, port);
+
+    let (response, detail) = match result {
+        Ok((response, timing)) => (response, timing.map_or(ProbeDetail::None, ProbeDetail::Timing)),
+        Err(e) => return ProbeReport::error(format!("{} 실패: {}", endpoint, e)),
+    };
+
+    if response.is_empty() {
+        return ProbeReport::failed(format!("{} 응답 없음", endpoint), Some(latency), detail);
+    }
+
+    let message = format!(
+        "{} 응답 \"{}\" ({}바이트, {}ms)",
+        endpoint,
+        preview(&response),
+        response.len(),
+        latency.as_millis()
+    );
+    if expectation.is_satisfied(&response) {
+        ProbeReport::ok(message, Some(latency), detail)
+    } else {
+        ProbeReport::failed(format!("{} - 예상 응답과 일치하지 않음", message), Some(latency), detail)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::decode_hex;
+
+    #[test]
+    fn decodes_hex_with_whitespace() {
+        assert_eq!(decode_hex("de ad\nBE EF").unwrap(), vec![0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(decode_hex("").unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn rejects_odd_length_and_non_hex_input() {
+        assert!(decode_hex("abc").is_err());
+        assert!(decode_hex("zz").is_err());
+        // 바이트 길이는 짝수지만 멀티바이트 문자가 섞인 입력도 패닉 없이 거부해야 합니다.
+        assert!(decode_hex("aéb").is_err());
+    }
+}
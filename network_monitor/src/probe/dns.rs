@@ -0,0 +1,277 @@
+use super::{ProbeDetail, ProbeReport};
+use crate::config::{Config, DnsProbe, NetworkTarget};
+use crate::network;
+use anyhow::{anyhow, Result};
+use hickory_proto::op::{Message, MessageType, OpCode, Query};
+use hickory_proto::rr::{Name, Record, RecordType};
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpStream, UdpSocket};
+use tokio::time::timeout;
+
+const DNS_PORT: u16 = 53;
+
+/// DNS 질의 한 번의 결과입니다.
+struct DnsAnswer {
+    server: SocketAddr,
+    rcode: String,
+    answers: Vec<String>,
+    latency: Duration,
+}
+
+/// RCODE 값을 표준 약어로 변환합니다.
+fn rcode_name(code: u16) -> String {
+    match code {
+        0 => "NOERROR".to_string(),
+        1 => "FORMERR".to_string(),
+        2 => "SERVFAIL".to_string(),
+        3 => "NXDOMAIN".to_string(),
+        4 => "NOTIMP".to_string(),
+        5 => "REFUSED".to_string(),
+        other => format!("RCODE{}", other),
+    }
+}
+
+/// `1.1.1.1`, `1.1.1.1:53`, `[2606:4700::1111]:53` 형식의 서버 주소를 해석합니다.
+fn parse_resolver(resolver: &str) -> Result<SocketAddr> {
+    if let Ok(addr) = SocketAddr::from_str(resolver) {
+        return Ok(addr);
+    }
+    let ip = resolver.trim_start_matches('[').trim_end_matches(']');
+    IpAddr::from_str(ip)
+        .map(|ip| SocketAddr::new(ip, DNS_PORT))
+        .map_err(|e| anyhow!("잘못된 DNS 서버 주소 '{}': {}", resolver, e))
+}
+
+/// 질의할 DNS 서버 목록을 반환합니다. 지정되지 않았으면 시스템 DNS 서버를 사용합니다.
+fn resolvers(probe: &DnsProbe) -> Result<Vec<SocketAddr>> {
+    match &probe.resolver {
+        Some(resolver) => Ok(vec![parse_resolver(resolver)?]),
+        None => {
+            let servers: Vec<SocketAddr> = network::system_dns_servers()?
+                .into_iter()
+                .map(|ip| SocketAddr::new(ip, DNS_PORT))
+                .collect();
+            if servers.is_empty() {
+                return Err(anyhow!("시스템 DNS 서버를 찾을 수 없음"));
+            }
+            Ok(servers)
+        }
+    }
+}
+
+fn build_query(name: &Name, record_type: RecordType) -> Result<(u16, Vec<u8>)> {
+    let id = rand::random::<u16>();
+    let mut message = Message::new();
+    message
+        .set_id(id)
+        .set_message_type(MessageType::Query)
+        .set_op_code(OpCode::Query)
+        .set_recursion_desired(true)
+        .add_query(Query::query(name.clone(), record_type));
+    let bytes = message
+        .to_vec()
+        .map_err(|e| anyhow!("DNS 질의 생성 실패: {}", e))?;
+    Ok((id, bytes))
+}
+
+/// UDP로 질의하고, 응답이 잘린 경우(TC) TCP로 다시 질의합니다.
+async fn query_server(
+    server: SocketAddr,
+    name: &Name,
+    record_type: RecordType,
+    timeout_duration: Duration,
+) -> Result<DnsAnswer> {
+    let (id, query) = build_query(name, record_type)?;
+    let start = Instant::now();
+
+    let bind_addr: SocketAddr = if server.is_ipv4() {
+        "0.0.0.0:0".parse()?
+    } else {
+        "[::]:0".parse()?
+    };
+    let socket = UdpSocket::bind(bind_addr).await?;
+    socket.connect(server).await?;
+    socket.send(&query).await?;
+
+    let mut buf = vec![0u8; 4096];
+    let response = loop {
+        let len = timeout(timeout_duration.saturating_sub(start.elapsed()), socket.recv(&mut buf))
+            .await
+            .map_err(|_| anyhow!("DNS 응답 시간 초과 ({})", server))??;
+        match Message::from_vec(&buf[..len]) {
+            Ok(message) if message.id() == id => break message,
+            _ => continue,
+        }
+    };
+
+    let response = if response.truncated() {
+        query_server_tcp(server, &query, timeout_duration.saturating_sub(start.elapsed())).await?
+    } else {
+        response
+    };
+
+    Ok(DnsAnswer {
+        server,
+        rcode: rcode_name(response.response_code().into()),
+        answers: answer_values(response.answers(), record_type),
+        latency: start.elapsed(),
+    })
+}
+
+/// 질의한 타입의 응답 값을 꺼냅니다.
+/// A/AAAA 질의에서도 CNAME 대상을 expected로 확인할 수 있도록 CNAME 응답을 함께 보관합니다.
+fn answer_values(records: &[Record], record_type: RecordType) -> Vec<String> {
+    records
+        .iter()
+        .filter(|record| record.record_type() == record_type || record.record_type() == RecordType::CNAME)
+        .filter_map(|record| record.data().map(|data| data.to_string()))
+        .collect()
+}
+
+async fn query_server_tcp(server: SocketAddr, query: &[u8], timeout_duration: Duration) -> Result<Message> {
+    let exchange = async {
+        let mut stream = TcpStream::connect(server).await?;
+        stream.write_all(&(query.len() as u16).to_be_bytes()).await?;
+        stream.write_all(query).await?;
+
+        let mut len = [0u8; 2];
+        stream.read_exact(&mut len).await?;
+        let mut buf = vec![0u8; usize::from(u16::from_be_bytes(len))];
+        stream.read_exact(&mut buf).await?;
+        Message::from_vec(&buf).map_err(|e| anyhow!("DNS 응답 해석 실패: {}", e))
+    };
+
+    timeout(timeout_duration, exchange)
+        .await
+        .map_err(|_| anyhow!("DNS TCP 응답 시간 초과 ({})", server))?
+}
+
+/// 이름 비교를 위해 대소문자와 끝의 점을 정규화합니다.
+fn normalize(value: &str) -> String {
+    value.trim().trim_end_matches('.').to_lowercase()
+}
+
+/// 응답 값에 없는 expected 항목 (대소문자와 끝의 점은 무시)
+fn missing_expected<'a>(answers: &[String], expected: &'a [String]) -> Vec<&'a str> {
+    let answers: Vec<String> = answers.iter().map(|a| normalize(a)).collect();
+    expected
+        .iter()
+        .filter(|expected| !answers.contains(&normalize(expected)))
+        .map(String::as_str)
+        .collect()
+}
+
+/// 지정된 DNS 서버(또는 시스템 DNS 서버)에 질의하고 RCODE와 응답 값을 확인합니다.
+pub async fn run(config: &Config, target: &NetworkTarget, probe: &DnsProbe) -> ProbeReport {
+    let record_type_name = probe.record_type.as_deref().unwrap_or("A").to_uppercase();
+    let record_type = match RecordType::from_str(&record_type_name) {
+        Ok(record_type) => record_type,
+        Err(e) => return ProbeReport::error(format!("잘못된 레코드 타입 '{}': {}", record_type_name, e)),
+    };
+    let name = match Name::from_utf8(&probe.name) {
+        Ok(name) => name,
+        Err(e) => return ProbeReport::error(format!("잘못된 도메인 이름 '{}': {}", probe.name, e)),
+    };
+    let servers = match resolvers(probe) {
+        Ok(servers) => servers,
+        Err(e) => return ProbeReport::error(e),
+    };
+
+    // 응답하는 첫 번째 서버의 결과를 사용
+    let timeout_duration = config.get_target_timeout(target);
+    let mut errors = Vec::new();
+    let mut answer = None;
+    for server in servers {
+        match query_server(server, &name, record_type, timeout_duration).await {
+            Ok(result) => {
+                answer = Some(result);
+                break;
+            }
+            Err(e) => errors.push(e.to_string()),
+        }
+    }
+    let answer = match answer {
+        Some(answer) => answer,
+        None => return ProbeReport::error(format!("DNS 질의 실패: {}", errors.join("; "))),
+    };
+
+    let summary = format!(
+        "{} {} @{} → {}, 응답 {}개 [{}] ({}ms)",
+        probe.name,
+        record_type_name,
+        answer.server,
+        answer.rcode,
+        answer.answers.len(),
+        answer.answers.join(", "),
+        answer.latency.as_millis()
+    );
+
+    // `ANY`는 서버가 응답하기만 하면 RCODE에 관계없이 정상으로 간주
+    let expected_rcode = probe.expected_rcode.as_deref().unwrap_or("NOERROR");
+    if !expected_rcode.eq_ignore_ascii_case("ANY") && !answer.rcode.eq_ignore_ascii_case(expected_rcode) {
+        return ProbeReport::failed(
+            format!("{} - 예상 RCODE {}", summary, expected_rcode.to_uppercase()),
+            Some(answer.latency),
+            ProbeDetail::None,
+        );
+    }
+
+    let missing = missing_expected(&answer.answers, &probe.expected);
+    if !missing.is_empty() {
+        return ProbeReport::failed(
+            format!("{} - 예상 값 없음: {}", summary, missing.join(", ")),
+            Some(answer.latency),
+            ProbeDetail::None,
+        );
+    }
+
+    ProbeReport::ok(summary, Some(answer.latency), ProbeDetail::None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hickory_proto::rr::rdata::{A, CNAME, TXT};
+    use hickory_proto::rr::RData;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    fn record(name: &str, data: RData) -> Record {
+        Record::from_rdata(Name::from_utf8(name).unwrap(), 300, data)
+    }
+
+    #[test]
+    fn parses_resolver_addresses() {
+        assert_eq!(parse_resolver("1.1.1.1").unwrap(), SocketAddr::new(Ipv4Addr::new(1, 1, 1, 1).into(), 53));
+        assert_eq!(parse_resolver("1.1.1.1:5353").unwrap(), SocketAddr::new(Ipv4Addr::new(1, 1, 1, 1).into(), 5353));
+        let v6: IpAddr = Ipv6Addr::new(0x2606, 0x4700, 0, 0, 0, 0, 0, 0x1111).into();
+        assert_eq!(parse_resolver("[2606:4700::1111]:53").unwrap(), SocketAddr::new(v6, 53));
+        assert_eq!(parse_resolver("[2606:4700::1111]").unwrap(), SocketAddr::new(v6, 53));
+        assert_eq!(parse_resolver("2606:4700::1111").unwrap(), SocketAddr::new(v6, 53));
+        assert!(parse_resolver("dns.example").is_err());
+    }
+
+    #[test]
+    fn normalize_ignores_case_and_trailing_dot() {
+        assert_eq!(normalize(" WWW.Example.COM. "), "www.example.com");
+        assert_eq!(normalize("192.0.2.1"), "192.0.2.1");
+    }
+
+    #[test]
+    fn keeps_cname_and_requested_type_answers() {
+        let records = [
+            record("www.example.com.", RData::CNAME(CNAME(Name::from_utf8("edge.example.net.").unwrap()))),
+            record("edge.example.net.", RData::A(A(Ipv4Addr::new(192, 0, 2, 1)))),
+            record("edge.example.net.", RData::TXT(TXT::new(vec!["v=spf1".to_string()]))),
+        ];
+        let answers = answer_values(&records, RecordType::A);
+        assert_eq!(answers, ["edge.example.net.", "192.0.2.1"]);
+
+        let expected = ["Edge.Example.Net".to_string(), "192.0.2.1".to_string()];
+        assert!(missing_expected(&answers, &expected).is_empty());
+        let expected = ["192.0.2.1".to_string(), "192.0.2.2".to_string()];
+        assert_eq!(missing_expected(&answers, &expected), ["192.0.2.2"]);
+    }
+}
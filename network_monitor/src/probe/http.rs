@@ -0,0 +1,79 @@
+use super::{ProbeDetail, ProbeReport};
+use crate::config::{Config, HttpProbe, NetworkTarget};
+use crate::network::http::{self, HttpRequest};
+use hyper::Method;
+use regex::Regex;
+use std::time::Instant;
+
+/// 확인할 URL을 반환합니다. 지정되지 않았으면 대상 주소로 만듭니다.
+pub fn probe_url(target: &NetworkTarget, probe: &HttpProbe) -> String {
+    probe
+        .url
+        .clone()
+        .unwrap_or_else(|| format!("http://{}/", target.address))
+}
+
+/// HTTP 요청을 보내고 상태 코드와 본문 조건을 확인합니다.
+/// 마지막 요청의 DNS/TCP/TLS/첫 바이트 시간을 세부 결과로 반환합니다.
+pub async fn run(config: &Config, target: &NetworkTarget, probe: &HttpProbe) -> ProbeReport {
+    let url = probe_url(target, probe);
+
+    let method_name = probe.method.as_deref().unwrap_or("GET").to_uppercase();
+    let method = match Method::from_bytes(method_name.as_bytes()) {
+        Ok(method) => method,
+        Err(e) => return ProbeReport::error(format!("잘못된 HTTP 메서드 '{}': {}", method_name, e)),
+    };
+    let body_regex = match probe.body_regex.as_deref().map(Regex::new).transpose() {
+        Ok(regex) => regex,
+        Err(e) => return ProbeReport::error(format!("잘못된 본문 정규식: {}", e)),
+    };
+
+    // 본문 조건이 있을 때만 본문을 읽음
+    let request = HttpRequest {
+        method,
+        headers: &probe.headers,
+        verify_tls: probe.verify_tls,
+        stop_on_status: &probe.expected_status,
+        read_body: probe.body_contains.is_some() || body_regex.is_some(),
+    };
+
+    let start = Instant::now();
+    let response = match http::send_request(&url, &request, config.get_target_timeout(target)).await {
+        Ok(response) => response,
+        Err(e) => return ProbeReport::error(format!("HTTP 요청 실패 ({}): {}", url, e)),
+    };
+    let latency = start.elapsed();
+    let status = response.status;
+    let detail = ProbeDetail::Timing(response.timing);
+
+    let status_ok = if probe.expected_status.is_empty() {
+        status.is_success()
+    } else {
+        probe.expected_status.contains(&status.as_u16())
+    };
+    if !status_ok {
+        return ProbeReport::failed(
+            format!("{} {} → 예상하지 않은 상태 코드 {}", method_name, url, status),
+            Some(latency),
+            detail,
+        );
+    }
+
+    if let Some(body) = &response.body {
+        let body = String::from_utf8_lossy(body);
+        let mismatch = match (&probe.body_contains, &body_regex) {
+            (Some(needle), _) if !body.contains(needle.as_str()) => Some(format!("본문에 '{}' 없음", needle)),
+            (_, Some(regex)) if !regex.is_match(&body) => Some(format!("본문이 정규식 '{}'와 일치하지 않음", regex)),
+            _ => None,
+        };
+        if let Some(reason) = mismatch {
+            return ProbeReport::failed(format!("{} {} → {}", method_name, url, reason), Some(latency), detail);
+        }
+    }
+
+    ProbeReport::ok(
+        format!("{} {} → {} ({}ms)", method_name, url, status, latency.as_millis()),
+        Some(latency),
+        detail,
+    )
+}
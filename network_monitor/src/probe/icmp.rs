@@ -0,0 +1,45 @@
+use super::{ProbeDetail, ProbeReport};
+use crate::config::{Config, NetworkTarget};
+use crate::network::{self, AddressPing};
+
+/// 대상 설정에 따라 주소를 해석하고, 주소마다 여러 개의 에코 요청을 보내 통계를 반환합니다.
+/// 주소 해석에 실패한 경우에만 오류를 반환합니다.
+async fn ping_target(config: &Config, target: &NetworkTarget) -> anyhow::Result<Vec<AddressPing>> {
+    network::ping_host_stats(
+        &target.address,
+        config.get_target_address_family(target),
+        config.get_target_packets(target),
+        config.get_target_packet_interval(target),
+        config.get_target_timeout(target),
+    )
+    .await
+}
+
+/// ICMP 확인을 수행합니다. 응답한 주소가 하나라도 있으면 성공입니다.
+pub async fn run(config: &Config, target: &NetworkTarget) -> ProbeReport {
+    let results = match ping_target(config, target).await {
+        Ok(results) => results,
+        Err(e) => return ProbeReport::error(e),
+    };
+
+    let message = results
+        .iter()
+        .map(|ping| match &ping.result {
+            Ok(stats) => format!("{} {}: {}", ping.family_label(), ping.address, stats),
+            Err(e) => format!("{} {}: {}", ping.family_label(), ping.address, e),
+        })
+        .collect::<Vec<_>>()
+        .join("; ");
+    let latency = primary_stats(&results).map(|stats| stats.avg);
+
+    if latency.is_some() {
+        ProbeReport::ok(message, latency, ProbeDetail::Icmp(results))
+    } else {
+        ProbeReport::failed(message, None, ProbeDetail::Icmp(results))
+    }
+}
+
+/// 응답한 첫 번째 주소의 통계를 반환합니다.
+pub fn primary_stats(results: &[AddressPing]) -> Option<&network::PingStats> {
+    results.iter().find_map(|ping| ping.result.as_ref().ok())
+}
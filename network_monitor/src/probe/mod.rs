@@ -0,0 +1,104 @@
+pub mod banner;
+pub mod dns;
+pub mod http;
+pub mod icmp;
+pub mod tls;
+
+use crate::config::{Config, NetworkTarget, Probe};
+use std::fmt;
+use std::time::Duration;
+
+/// 확인 결과의 상태입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbeStatus {
+    Ok,
+    /// 응답은 정상이지만 주의가 필요한 상태 (예: 인증서 만료 임박)
+    Warning,
+    Failed,
+}
+
+/// 확인 방식별 세부 결과입니다.
+#[derive(Debug, Clone)]
+pub enum ProbeDetail {
+    None,
+    Icmp(Vec<crate::network::AddressPing>),
+    /// TCP/TLS/HTTP 연결의 단계별 소요 시간
+    Timing(crate::network::ConnectionTiming),
+}
+
+/// 확인 방식에 관계없이 모니터링 루프가 사용하는 공통 결과입니다.
+#[derive(Debug, Clone)]
+#[cfg_attr(not(feature = "gui"), allow(dead_code))]
+pub struct ProbeReport {
+    pub status: ProbeStatus,
+    pub message: String,
+    pub latency: Option<Duration>,
+    pub detail: ProbeDetail,
+}
+
+impl ProbeReport {
+    pub fn ok(message: String, latency: Option<Duration>, detail: ProbeDetail) -> Self {
+        Self {
+            status: ProbeStatus::Ok,
+            message,
+            latency,
+            detail,
+        }
+    }
+
+    pub fn warning(message: String, latency: Option<Duration>, detail: ProbeDetail) -> Self {
+        Self {
+            status: ProbeStatus::Warning,
+            message,
+            latency,
+            detail,
+        }
+    }
+
+    pub fn failed(message: String, latency: Option<Duration>, detail: ProbeDetail) -> Self {
+        Self {
+            status: ProbeStatus::Failed,
+            message,
+            latency,
+            detail,
+        }
+    }
+
+    /// 확인 이전 단계(주소 해석, 클라이언트 생성 등)에서 실패한 경우의 결과를 만듭니다.
+    pub fn error(error: impl fmt::Display) -> Self {
+        Self::failed(error.to_string(), None, ProbeDetail::None)
+    }
+
+    /// 재시도/복구 판단에서 성공으로 취급하는지 여부
+    pub fn is_success(&self) -> bool {
+        self.status != ProbeStatus::Failed
+    }
+}
+
+impl fmt::Display for ProbeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// 확인 방식의 이름을 반환합니다.
+pub fn probe_kind(target: &NetworkTarget) -> &'static str {
+    match &target.probe {
+        None | Some(Probe::Icmp) => "icmp",
+        Some(Probe::Http(_)) => "http",
+        Some(Probe::Dns(_)) => "dns",
+        Some(Probe::Tls(_)) => "tls",
+        Some(Probe::Banner(_)) => "banner",
+    }
+}
+
+/// 대상에 설정된 확인 방식으로 상태를 확인합니다.
+pub async fn run_probe(config: &Config, target: &NetworkTarget) -> ProbeReport {
+    match &target.probe {
+        None | Some(Probe::Icmp) => icmp::run(config, target).await,
+        Some(Probe::Http(probe)) => http::run(config, target, probe).await,
+        Some(Probe::Dns(probe)) => dns::run(config, target, probe).await,
+        Some(Probe::Tls(probe)) => tls::run(config, target, probe).await,
+        Some(Probe::Banner(probe)) => banner::run(config, target, probe).await,
+    }
+}
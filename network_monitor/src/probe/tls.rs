@@ -0,0 +1,189 @@
+use super::{ProbeDetail, ProbeReport};
+use crate::config::{Config, NetworkTarget, TlsProbe};
+use crate::network::timing::{self, ConnectionTiming};
+use anyhow::{anyhow, Result};
+use std::time::{Duration, Instant};
+use x509_parser::extensions::GeneralName;
+use x509_parser::prelude::{FromDer, X509Certificate};
+
+const DEFAULT_TLS_PORT: u16 = 443;
+const DEFAULT_WARNING_DAYS: i64 = 14;
+
+/// 리프 인증서에서 추출한 정보입니다.
+struct CertificateInfo {
+    issuer: String,
+    sans: Vec<String>,
+    not_after: i64,
+}
+
+/// TCP 연결 후 TLS 핸드셰이크를 수행하고 리프 인증서(DER)와 단계별 시간을 반환합니다.
+/// `verify`가 false이면 체인과 호스트 이름 검증을 건너뜁니다.
+async fn handshake(
+    host: &str,
+    port: u16,
+    server_name: &str,
+    verify: bool,
+    timeout_duration: Duration,
+) -> Result<(Vec<u8>, ConnectionTiming)> {
+    let (tls_stream, timing) = timing::connect_tls(host, port, server_name, verify, timeout_duration).await?;
+
+    let der = tls_stream
+        .get_ref()
+        .peer_certificate()
+        .map_err(|e| anyhow!("인증서 가져오기 실패: {}", e))?
+        .ok_or_else(|| anyhow!("서버가 인증서를 제공하지 않음"))?
+        .to_der()
+        .map_err(|e| anyhow!("인증서 인코딩 실패: {}", e))?;
+    Ok((der, timing))
+}
+
+/// TLS 핸드셰이크 단계에서 발생한 오류(인증서 체인/호스트 이름 검증 실패 등)인지 확인합니다.
+/// TCP 연결 실패나 시간 초과는 해당하지 않습니다.
+fn is_handshake_error(error: &anyhow::Error) -> bool {
+    error.downcast_ref::<native_tls::Error>().is_some()
+}
+
+fn parse_certificate(der: &[u8]) -> Result<CertificateInfo> {
+    let (_, cert) =
+        X509Certificate::from_der(der).map_err(|e| anyhow!("인증서 해석 실패: {}", e))?;
+
+    let sans = match cert.subject_alternative_name() {
+        Ok(Some(extension)) => extension
+            .value
+            .general_names
+            .iter()
+            .filter_map(|name| match name {
+                GeneralName::DNSName(dns) => Some(dns.to_string()),
+                GeneralName::IPAddress(bytes) => match bytes.len() {
+                    4 => Some(std::net::Ipv4Addr::from(<[u8; 4]>::try_from(*bytes).ok()?).to_string()),
+                    16 => Some(std::net::Ipv6Addr::from(<[u8; 16]>::try_from(*bytes).ok()?).to_string()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    Ok(CertificateInfo {
+        issuer: cert.issuer().to_string(),
+        sans,
+        not_after: cert.validity().not_after.timestamp(),
+    })
+}
+
+/// `address:port`에 TLS 핸드셰이크를 수행하고 리프 인증서의 만료일, 발급자, SAN,
+/// 체인 검증 결과를 확인합니다.
+pub async fn run(config: &Config, target: &NetworkTarget, probe: &TlsProbe) -> ProbeReport {
+    let port = probe.port.or(target.port).unwrap_or(DEFAULT_TLS_PORT);
+    let server_name = probe
+        .server_name
+        .clone()
+        .unwrap_or_else(|| target.address.trim_start_matches('[').trim_end_matches(']').to_string());
+    let timeout_duration = config.get_target_timeout(target);
+    let endpoint = format!("{}:{}", target.address, port);
+
+    // 인증서/호스트 이름 검증 때문에 핸드셰이크가 실패한 경우에만 인증서 정보를 얻기 위해
+    // 검증 없이 다시 연결하고, 연결 실패나 시간 초과는 그대로 보고
+    let start = Instant::now();
+    let ((der, timing), chain_result) = match handshake(&target.address, port, &server_name, true, timeout_duration).await {
+        Ok(handshake) => (handshake, Ok(())),
+        Err(verify_err) if is_handshake_error(&verify_err) => {
+            match handshake(&target.address, port, &server_name, false, timeout_duration).await {
+                Ok(handshake) => (handshake, Err(verify_err.to_string())),
+                Err(e) => return ProbeReport::error(format!("TLS 핸드셰이크 실패 ({}): {}", endpoint, e)),
+            }
+        }
+        Err(e) => return ProbeReport::error(format!("TLS 연결 실패 ({}): {}", endpoint, e)),
+    };
+    let latency = start.elapsed();
+
+    let cert = match parse_certificate(&der) {
+        Ok(cert) => cert,
+        Err(e) => return ProbeReport::error(e),
+    };
+
+    let days_left = (cert.not_after - chrono::Utc::now().timestamp()).div_euclid(86_400);
+    let expiry = chrono::DateTime::from_timestamp(cert.not_after, 0)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| cert.not_after.to_string());
+    let chain_text = match &chain_result {
+        Ok(()) => "체인 검증 성공".to_string(),
+        Err(e) => format!("체인 검증 실패: {}", e),
+    };
+    let message = format!(
+        "{} ({}) 인증서 만료 {} ({}일 남음), 발급자 {}, SAN [{}], {}",
+        endpoint,
+        server_name,
+        expiry,
+        days_left,
+        cert.issuer,
+        cert.sans.join(", "),
+        chain_text
+    );
+
+    let detail = ProbeDetail::Timing(timing);
+    let warning_days = probe.warning_days.unwrap_or(DEFAULT_WARNING_DAYS);
+    if days_left < 0 {
+        ProbeReport::failed(format!("인증서 만료됨 - {}", message), Some(latency), detail)
+    } else if chain_result.is_err() {
+        ProbeReport::failed(message, Some(latency), detail)
+    } else if days_left <= warning_days {
+        ProbeReport::warning(
+            format!("인증서 만료 임박 ({}일 이내) - {}", warning_days, message),
+            Some(latency),
+            detail,
+        )
+    } else {
+        ProbeReport::ok(message, Some(latency), detail)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::TcpListener;
+
+    /// 연결을 받아 `reply`를 보낸 뒤 `hold` 동안 연결을 유지하는 서버의 포트
+    async fn server(reply: &'static [u8], hold: Duration) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            stream.write_all(reply).await.unwrap();
+            tokio::time::sleep(hold).await;
+        });
+        port
+    }
+
+    async fn handshake_error(port: u16) -> anyhow::Error {
+        handshake("127.0.0.1", port, "localhost", true, Duration::from_millis(300))
+            .await
+            .err()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn connect_error_is_not_handshake_error() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+        assert!(!is_handshake_error(&handshake_error(port).await));
+    }
+
+    #[tokio::test]
+    async fn timeout_is_not_handshake_error() {
+        let port = server(b"", Duration::from_secs(5)).await;
+        let error = handshake_error(port).await;
+        assert!(error.to_string().contains("시간 초과"), "{}", error);
+        assert!(!is_handshake_error(&error));
+    }
+
+    #[tokio::test]
+    async fn tls_failure_is_handshake_error() {
+        let port = server(b"HTTP/1.1 400 Bad Request\r\n\r\n", Duration::ZERO).await;
+        let error = handshake_error(port).await;
+        assert!(is_handshake_error(&error), "{}", error);
+    }
+}
@@ -1,12 +1,12 @@
-use log::{LevelFilter, SetLoggerError};
+use log::SetLoggerError;
 use std::fs::File;
-use std::io::Write;
-use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Once;
 
 // 디버그 모드 상태를 저장하는 전역 변수
 static DEBUG_MODE: AtomicBool = AtomicBool::new(false);
+// 복구 작업을 실행하지 않고 기록만 하는 모드
+static DRY_RUN_MODE: AtomicBool = AtomicBool::new(false);
 static LOGGER_INIT: Once = Once::new();
 
 pub mod logging {
@@ -84,44 +84,12 @@ pub fn set_debug_mode(enabled: bool) {
     DEBUG_MODE.store(enabled, Ordering::Relaxed);
 }
 
-/// 디버그 모드 상태를 반환합니다.
-pub fn is_debug_mode() -> bool {
-    DEBUG_MODE.load(Ordering::Relaxed)
+/// 복구 작업 모의 실행(dry-run) 모드를 설정합니다.
+pub fn set_dry_run_mode(enabled: bool) {
+    DRY_RUN_MODE.store(enabled, Ordering::Relaxed);
 }
 
-/// 파일이 존재하는지 확인합니다.
-pub fn file_exists<P: AsRef<Path>>(path: P) -> bool {
-    path.as_ref().exists()
-}
-
-/// 현재 실행 파일의 경로를 반환합니다.
-pub fn get_executable_path() -> Result<std::path::PathBuf, std::io::Error> {
-    std::env::current_exe()
-}
-
-/// 현재 실행 파일의 디렉토리 경로를 반환합니다.
-pub fn get_executable_dir() -> Result<std::path::PathBuf, std::io::Error> {
-    let exe_path = get_executable_path()?;
-    let dir = exe_path.parent().ok_or_else(|| {
-        std::io::Error::new(
-            std::io::ErrorKind::NotFound,
-            "실행 파일의 디렉토리를 찾을 수 없습니다.",
-        )
-    })?;
-    Ok(dir.to_path_buf())
-}
-
-/// 파일 경로가 절대 경로인지 확인합니다.
-pub fn is_absolute_path<P: AsRef<Path>>(path: P) -> bool {
-    path.as_ref().is_absolute()
-}
-
-/// 상대 경로를 절대 경로로 변환합니다.
-pub fn to_absolute_path<P: AsRef<Path>>(path: P) -> Result<std::path::PathBuf, std::io::Error> {
-    if is_absolute_path(&path) {
-        return Ok(path.as_ref().to_path_buf());
-    }
-
-    let exe_dir = get_executable_dir()?;
-    Ok(exe_dir.join(path))
+/// 복구 작업 모의 실행(dry-run) 모드 상태를 반환합니다.
+pub fn is_dry_run_mode() -> bool {
+    DRY_RUN_MODE.load(Ordering::Relaxed)
 }